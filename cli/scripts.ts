@@ -29,6 +29,9 @@ import {
   TEST_TOKEN_SUPPLY,
   TEST_VIRTUAL_RESERVES,
   TEST_LIMIT_TIMESTAMP,
+  TEST_TIMELOCK_DELAY,
+  TEST_GRADUATION_THRESHOLD,
+  MOCK_AMM_PROGRAM_ID,
   TEST_INITIAL_VIRTUAL_TOKEN_RESERVES,
   TEST_INITIAL_VIRTUAL_SOL_RESERVES,
   TEST_INITIAL_REAL_TOKEN_RESERVES,
@@ -91,7 +94,7 @@ export const configProject = async () => {
   const newConfig = {
     authority: payer.publicKey,
     pendingAuthority: PublicKey.default,
-    backendSignAuthority: payer.publicKey,
+    curator: payer.publicKey,
 
     teamWallet: payer.publicKey,
     teamWallet2: payer.publicKey,
@@ -122,6 +125,49 @@ export const configProject = async () => {
     minSolLiquidity: new BN(5_000),
 
     initialized: true,
+
+    paused: 0,
+
+    guardian: payer.publicKey,
+    whitelistManager: payer.publicKey,
+
+    pendingGuardian: PublicKey.default,
+    pendingCurator: PublicKey.default,
+    pendingWhitelistManager: PublicKey.default,
+
+    timelockDelay: new BN(TEST_TIMELOCK_DELAY),
+    configChangeNonce: new BN(0),
+
+    feeTiers: [
+      // partners
+      { platformBuyFee: new BN(50), platformSellFee: new BN(50), limitTimestamp: new BN(0) },
+      // market makers
+      { platformBuyFee: new BN(20), platformSellFee: new BN(20), limitTimestamp: new BN(0) },
+      // promotional
+      { platformBuyFee: new BN(0), platformSellFee: new BN(0), limitTimestamp: new BN(TEST_LIMIT_TIMESTAMP) },
+    ],
+
+    whitelistMerkleRoot: new Array(32).fill(0),
+
+    // no per wallet limits by default
+    maxWalletSol: new BN(0),
+    maxWalletShareBps: new BN(0),
+
+    // buys capped at 1 SOL per tx and 2 SOL per wallet during the first minute
+    launchPhase: {
+      duration: new BN(60),
+      maxBuySol: new BN(1_000_000_000),
+      maxWalletSol: new BN(2_000_000_000),
+      whitelistOnly: false,
+    },
+
+    graduationThreshold: new BN(TEST_GRADUATION_THRESHOLD),
+    migrationAuthority: payer.publicKey,
+    ammProgram: MOCK_AMM_PROGRAM_ID,
+
+    maxMarketInfoLen: 200,
+    legacySolLiabilities: new BN(0),
+    totalSolLiabilities: new BN(0),
   };
   const tx = await createConfigTx(
     payer.publicKey,
@@ -163,6 +209,7 @@ export const createMarket = async () => {
 
     payer.publicKey,
     configAccount.teamWallet,
+    configAccount.teamWallet2,
     noTokenMintTx.no_tokenKp.publicKey,
 
 
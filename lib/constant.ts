@@ -3,7 +3,10 @@ import { Cluster, PublicKey } from "@solana/web3.js";
 
 export const SEED_CONFIG = "config";
 export const SEED_MARKET = "market";
+export const SEED_CATEGORICAL_MARKET = "categorical_market";
 export const SEED_WHITELIST = "whitelist";
+export const SEED_POSITION = "position";
+export const SEED_MARKET_TOMBSTONE = "market_tombstone";
 export const CREATOR = "creator";
 
 export const TEST_YES_NAME = "Agree";
@@ -23,6 +26,11 @@ export const TEST_TOKEN_SUPPLY = 1_000_000_000_000_000;
 export const TEST_DECIMALS = 6;
 
 export const TEST_LIMIT_TIMESTAMP = 2_592_000; // 3600 * 24 * 30
+export const TEST_TIMELOCK_DELAY = 86_400; // 3600 * 24
+export const TEST_GRADUATION_THRESHOLD = 85_000_000_000; // 85 SOL
+
+// local migration target, see programs/mock-amm
+export const MOCK_AMM_PROGRAM_ID = new PublicKey("ABPcStkaTDv2DJY3mG4zykq4HyvdaVXFanq2zyZmDtvK");
 
 export const TEST_INITIAL_VIRTUAL_TOKEN_RESERVES = 1_000_000_000_000_000;
 export const TEST_INITIAL_VIRTUAL_SOL_RESERVES = 20_000_000_000;
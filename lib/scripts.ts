@@ -15,12 +15,16 @@ import {
   feeDestination,
   marketProgram,
   SEED_MARKET,
+  SEED_MARKET_TOMBSTONE,
   SEED_CONFIG,
+  SEED_POSITION,
 } from "./constant";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
 
 import crypto from 'crypto';
@@ -64,10 +68,13 @@ export const createMarketTx = async (
 
   user: PublicKey,      //user pubkey
   teamWallet: PublicKey,//teamWallet pubkey  
+  teamWallet2: PublicKey,//teamWallet2 pubkey
   noToken: PublicKey,   //noToken pubkey
 
   connection: Connection,
-  program: Program<Takesfun>
+  program: Program<Takesfun>,
+  tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID,  //token program of the outcome mints
+  soulbound: boolean = false  //non transferable outcome tokens, must match the no token
 ) => {
   const yes_tokenKp = Keypair.generate();
   const no_tokenKp = Keypair.generate();
@@ -80,13 +87,19 @@ export const createMarketTx = async (
       //  metadata
       yes_symbol,
       yes_uri,
+      soulbound,
       market_info,
+      null, // binary market
+      null, // no initial buy
     )
     .accounts({
       yesToken: yes_tokenKp.publicKey,
       noToken,//no_tokenKp.publicKey,
       creator: user,
       teamWallet: teamWallet,
+      teamWallet2: teamWallet2,
+      whitelist: null,  // creators without a whitelist entry buy at standard fees
+      tokenProgram,
     })
     .transaction();
 
@@ -98,6 +111,85 @@ export const createMarketTx = async (
   return { tx, yes_tokenKp };
 };
 
+export const createMarketSecondTx = async (
+
+  yes_symbol: string,   //Yes Token Symbol
+  yes_uri: string,      //Yes Token Uri
+
+  market_info: string,  //market info, seeds the market pda
+
+  user: PublicKey,      //user pubkey, pays for the market
+  curator: PublicKey,   //curator pubkey, co-signs the creation
+  creator: PublicKey,   //creator wallet, receives the initial buy
+  teamWallet: PublicKey,//teamWallet pubkey
+  teamWallet2: PublicKey,//teamWallet2 pubkey
+  noToken: PublicKey,   //noToken pubkey
+
+  initialBuy: { tokenType: number; amount: BN; minimumReceiveAmount: BN } | null,
+
+  connection: Connection,
+  program: Program<Takesfun>,
+  tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID,  //token program of the outcome mints
+  soulbound: boolean = false  //non transferable outcome tokens, must match the no token
+) => {
+  const yes_tokenKp = Keypair.generate();
+
+  const market_info_hash = crypto.createHash('sha256').update(market_info, 'utf-8').digest();
+  const [marketPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEED_MARKET), market_info_hash],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .createMarketSecond(
+      market_info,
+      //  metadata
+      yes_symbol,
+      yes_uri,
+      soulbound,
+      creator,
+      null, // binary market
+      initialBuy,
+    )
+    .accounts({
+      firstClient: user,
+      admin: curator,
+      creator,
+      yesToken: yes_tokenKp.publicKey,
+      noToken,
+      //@ts-ignore
+      market: marketPDA,
+      marketTombstone: PublicKey.findProgramAddressSync(
+        [Buffer.from(SEED_MARKET_TOMBSTONE), marketPDA.toBytes()],
+        program.programId
+      )[0],
+      teamWallet,
+      teamWallet2,
+      creatorYesAta: getAssociatedTokenAddressSync(yes_tokenKp.publicKey, creator, true, tokenProgram),
+      creatorNoAta: getAssociatedTokenAddressSync(noToken, creator, true, tokenProgram),
+      position: PublicKey.findProgramAddressSync(
+        [Buffer.from(SEED_POSITION), marketPDA.toBytes(), creator.toBytes()],
+        program.programId
+      )[0],
+      whitelist: null,  // creators without a whitelist entry buy at standard fees
+      collateralMint: null,
+      globalCollateralAta: null,
+      firstClientCollateralAta: null,
+      teamWalletCollateralAta: null,
+      teamWallet2CollateralAta: null,
+      creatorVaultCollateralAta: null,
+      collateralTokenProgram: null,
+      tokenProgram,
+    })
+    .transaction();
+
+  tx.feePayer = user;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+  tx.sign(yes_tokenKp);
+
+  return { tx, yes_tokenKp, market: marketPDA };
+};
+
 export const mintNoTokenTx = async (
 
   no_symbol: string,  //no Token Symbol
@@ -106,7 +198,9 @@ export const mintNoTokenTx = async (
   user: PublicKey,    //user pubkey
 
   connection: Connection,
-  program: Program<Takesfun>
+  program: Program<Takesfun>,
+  tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID,  //token program of the outcome mint
+  soulbound: boolean = false  //non transferable outcome tokens
 ) => {
 
   const no_tokenKp = Keypair.generate();
@@ -118,10 +212,12 @@ export const mintNoTokenTx = async (
       //  metadata
       no_symbol,
       no_uri,
+      soulbound,
     )
     .accounts({
       noToken: no_tokenKp.publicKey,
       creator: user,
+      tokenProgram,
     })
     .transaction();
   tx.feePayer = user;
@@ -143,7 +239,8 @@ export const swapTx = async (
   token_type: number,
 
   connection: Connection,
-  program: Program<Takesfun>
+  program: Program<Takesfun>,
+  tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID  //token program of the outcome mints
 ) => {
   const configPda = PublicKey.findProgramAddressSync(
     [Buffer.from(SEED_CONFIG)],
@@ -158,7 +255,7 @@ export const swapTx = async (
   const marketAccount = await program.account.market.fetch(marketPda);
 
   const tx = await program.methods
-    .swap(new BN(amount), style, token_type, new BN(0))
+    .swap(new BN(amount), style, token_type, new BN(0), null)
     .accounts({
       teamWallet: configAccount.teamWallet,
       teamWallet2: configAccount.teamWallet2,
@@ -166,6 +263,7 @@ export const swapTx = async (
       user,
       noToken: no_token,
       yesToken: yes_token,
+      tokenProgram,
     })
     .transaction();
 
@@ -247,7 +345,8 @@ export const swapSecondTx = async (
   token_type: number,
 
   connection: Connection,
-  program: Program<Takesfun>
+  program: Program<Takesfun>,
+  tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID  //token program of the outcome mints
 ) => {
   const configPda = PublicKey.findProgramAddressSync(
     [Buffer.from(SEED_CONFIG)],
@@ -268,7 +367,7 @@ export const swapSecondTx = async (
   const marketAccount = await program.account.market.fetch(marketPDA);
 
   const tx = await program.methods
-    .swapSecond(market_info, new BN(amount), style, token_type, new BN(0))
+    .swapSecond(market_info, new BN(amount), style, token_type, new BN(0), null)
     .accounts({
       teamWallet: configAccount.teamWallet,
       teamWallet2: configAccount.teamWallet2,
@@ -276,6 +375,7 @@ export const swapSecondTx = async (
       user,
       noToken: no_token,
       yesToken: yes_token,
+      tokenProgram,
       //@ts-ignore
       market: marketPDA,
     })
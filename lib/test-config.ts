@@ -0,0 +1,79 @@
+//  config shared by the localnet tests, the config pda can only be created once per validator
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+
+import { Takesfun } from "../target/types/takesfun";
+import { createConfigTx } from "./scripts";
+import {
+  SEED_CONFIG,
+  TEST_INITIAL_REAL_TOKEN_RESERVES,
+  TEST_INITIAL_VIRTUAL_SOL_RESERVES,
+  TEST_INITIAL_VIRTUAL_TOKEN_RESERVES,
+  TEST_TIMELOCK_DELAY,
+} from "./constant";
+
+export const GRADUATION_THRESHOLD = 2_000_000_000; // 2 SOL
+
+export const testConfig = (admin: PublicKey, ammProgram: PublicKey) => ({
+  authority: admin,
+  pendingAuthority: PublicKey.default,
+  curator: admin,
+  teamWallet: admin,
+  teamWallet2: admin,
+  platformBuyFee: new BN(100),
+  platformSellFee: new BN(100),
+  platformBuySmallFee: new BN(80),
+  platformSellSmallFee: new BN(80),
+  creatorBuyFee: new BN(20),
+  creatorSellFee: new BN(20),
+  tokenSupplyConfig: new BN(TEST_INITIAL_REAL_TOKEN_RESERVES),
+  tokenDecimalsConfig: 6,
+  initialVirtualYesTokenReservesConfig: new BN(TEST_INITIAL_VIRTUAL_TOKEN_RESERVES),
+  initialVirtualYesSolReservesConfig: new BN(TEST_INITIAL_VIRTUAL_SOL_RESERVES),
+  initialRealYesTokenReservesConfig: new BN(TEST_INITIAL_REAL_TOKEN_RESERVES),
+  initialVirtualNoTokenReservesConfig: new BN(TEST_INITIAL_VIRTUAL_TOKEN_RESERVES),
+  initialVirtualNoSolReservesConfig: new BN(TEST_INITIAL_VIRTUAL_SOL_RESERVES),
+  initialRealNoTokenReservesConfig: new BN(TEST_INITIAL_REAL_TOKEN_RESERVES),
+  limitTimestamp: new BN(0),
+  crossSolFactor: 0.2,
+  minSolLiquidity: new BN(5_000),
+  initialized: true,
+  paused: 0,
+  guardian: admin,
+  whitelistManager: admin,
+  pendingGuardian: PublicKey.default,
+  pendingCurator: PublicKey.default,
+  pendingWhitelistManager: PublicKey.default,
+  timelockDelay: new BN(TEST_TIMELOCK_DELAY),
+  configChangeNonce: new BN(0),
+  feeTiers: [0, 1, 2].map(() => ({
+    platformBuyFee: new BN(0),
+    platformSellFee: new BN(0),
+    limitTimestamp: new BN(0),
+  })),
+  whitelistMerkleRoot: new Array(32).fill(0),
+  maxWalletSol: new BN(0),
+  maxWalletShareBps: new BN(0),
+  launchPhase: { duration: new BN(0), maxBuySol: new BN(0), maxWalletSol: new BN(0), whitelistOnly: false },
+  graduationThreshold: new BN(GRADUATION_THRESHOLD),
+  migrationAuthority: admin,
+  ammProgram,
+  maxMarketInfoLen: 200,
+  legacySolLiabilities: new BN(0),
+  totalSolLiabilities: new BN(0),
+});
+
+export const ensureTestConfig = async (
+  provider: anchor.AnchorProvider,
+  program: Program<Takesfun>,
+  ammProgram: PublicKey
+) => {
+  const configPda = PublicKey.findProgramAddressSync([Buffer.from(SEED_CONFIG)], program.programId)[0];
+  if (await provider.connection.getAccountInfo(configPda)) {
+    return;
+  }
+  const admin = provider.wallet.publicKey;
+  const tx = await createConfigTx(admin, testConfig(admin, ammProgram), provider.connection, program);
+  await provider.sendAndConfirm(tx);
+};
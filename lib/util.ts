@@ -12,6 +12,8 @@ import {
 
 import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "@coral-xyz/anchor";
+import crypto from "crypto";
 
 export const getAssociatedTokenAccount = (
     ownerPubkey: PublicKey,
@@ -151,3 +153,50 @@ export const getATokenAccountsNeedCreate = async (
         destinationAccounts,
     };
 };
+
+// Whitelist merkle tree, must match utils::merkle_leaf / verify_merkle_proof
+const sha256 = (...parts: Buffer[]): Buffer =>
+    crypto.createHash("sha256").update(Buffer.concat(parts)).digest();
+
+export const whitelistLeaf = (user: PublicKey, tier: number, expiry: number): Buffer =>
+    sha256(
+        Buffer.from([0]),
+        user.toBuffer(),
+        Buffer.from([tier]),
+        new BN(expiry).toArrayLike(Buffer, "le", 8)
+    );
+
+const hashPair = (a: Buffer, b: Buffer): Buffer =>
+    Buffer.compare(a, b) <= 0
+        ? sha256(Buffer.from([1]), a, b)
+        : sha256(Buffer.from([1]), b, a);
+
+// Returns the root and a proof for every leaf index
+export const buildWhitelistTree = (leaves: Buffer[]) => {
+    const proofs: Buffer[][] = leaves.map(() => []);
+    let level = leaves.map((leaf, index) => ({ hash: leaf, indices: [index] }));
+
+    while (level.length > 1) {
+        const next = [];
+        for (let i = 0; i < level.length; i += 2) {
+            const left = level[i];
+            const right = level[i + 1];
+            if (!right) {
+                next.push(left);
+                continue;
+            }
+            left.indices.forEach((index) => proofs[index].push(right.hash));
+            right.indices.forEach((index) => proofs[index].push(left.hash));
+            next.push({
+                hash: hashPair(left.hash, right.hash),
+                indices: [...left.indices, ...right.indices],
+            });
+        }
+        level = next;
+    }
+
+    return {
+        root: level.length ? level[0].hash : Buffer.alloc(32),
+        proofs: proofs.map((proof) => proof.map((node) => Array.from(node))),
+    };
+};
@@ -1,12 +1,23 @@
 pub const CONFIG: &str = "config";
+pub const CONFIG_CHANGE: &str = "config_change";
 pub const GLOBAL: &str = "global";
 pub const CREATOR: &str = "creator";
 pub const MARKET: &str = "market";
+pub const MARKET_VAULT: &str = "market_vault";
+pub const MARKET_METADATA: &str = "market_metadata";
+pub const MARKET_TOMBSTONE: &str = "market_tombstone";
+pub const CATEGORICAL_MARKET: &str = "categorical_market";
 pub const WHITELIST: &str = "whitelist";
-pub const METADATA: &str = "metadata";
+pub const POSITION: &str = "position";
+//  pool seed of the amm program markets migrate to
+pub const AMM_POOL: &str = "pool";
 pub const YES_NAME: &str = "yes";
 pub const NO_NAME: &str = "no";
 pub const LAMPORT_DECIMALS: u8 = 9;
+//  resolved markets can be closed with unredeemed tokens left after this many seconds
+pub const CLOSE_GRACE_PERIOD: i64 = 180 * 24 * 60 * 60;
+//  a proposed resolution can be challenged for this many seconds before it is applied
+pub const RESOLUTION_CHALLENGE_PERIOD: i64 = 24 * 60 * 60;
 pub const TOKEN_LAUNCH: &str = "token_launch";
 pub const VIRTUAL_SOL: f64 = 20.0;
 pub const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0; // Convert SOL to lamports
@@ -105,4 +105,121 @@ pub enum TakesFunError {
 
     #[msg("incorrect no token")]
     IncorrectNoToken,
+
+    #[msg("Config is already initialized")]
+    ConfigAlreadyInitialized,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Market is frozen")]
+    MarketFrozen,
+
+    #[msg("Timelock of the config change has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Invalid whitelist merkle proof")]
+    InvalidWhitelistProof,
+
+    #[msg("Whitelist account is required to activate a merkle proof")]
+    MissingWhitelistAccount,
+
+    #[msg("Whitelist entry is still active")]
+    WhitelistStillActive,
+
+    #[msg("Position account is required when the market has position limits")]
+    MissingPositionAccount,
+
+    #[msg("Position limit of the market exceeded")]
+    PositionLimitExceeded,
+
+    #[msg("Invalid outcome")]
+    InvalidOutcome,
+
+    #[msg("Not all outcomes of the market are created")]
+    OutcomesNotCreated,
+
+    #[msg("Market is already resolved")]
+    MarketResolved,
+
+    #[msg("Market is not resolved")]
+    MarketNotResolved,
+
+    #[msg("Outcome mints are created under token-2022")]
+    UnsupportedTokenProgram,
+
+    #[msg("Collateral token accounts are required for spl collateral markets")]
+    MissingCollateralAccount,
+
+    #[msg("Incorrect collateral mint")]
+    IncorrectCollateralMint,
+
+    #[msg("Not supported for spl collateral markets")]
+    UnsupportedCollateral,
+
+    #[msg("Market reserves are still in the global vault")]
+    MarketVaultNotMigrated,
+
+    #[msg("Market vault already migrated")]
+    MarketVaultAlreadyMigrated,
+
+    #[msg("Market can not be closed yet")]
+    MarketNotClosable,
+
+    #[msg("Market layout is already up to date")]
+    MarketAlreadyUpgraded,
+
+    #[msg("Market info length is outside the configured range")]
+    InvalidMarketInfoLength,
+
+    #[msg("Market info must be NFC normalized without leading or trailing whitespace")]
+    MarketInfoNotNormalized,
+
+    #[msg("Invalid market metadata")]
+    InvalidMarketMetadata,
+
+    #[msg("Market metadata can only be changed before the first trade")]
+    MarketAlreadyTraded,
+
+    #[msg("Config is already migrated")]
+    ConfigAlreadyMigrated,
+
+    #[msg("Whitelist entry was revoked")]
+    WhitelistRevoked,
+
+    #[msg("Only whitelisted users can buy during the launch phase")]
+    LaunchPhaseWhitelistOnly,
+
+    #[msg("Buy exceeds the launch phase max buy")]
+    LaunchPhaseMaxBuyExceeded,
+
+    #[msg("Wallet exceeds the launch phase max wallet")]
+    LaunchPhaseMaxWalletExceeded,
+
+    #[msg("Initial buy account is missing")]
+    MissingInitialBuyAccount,
+
+    #[msg("Market already has a resolution proposal")]
+    ResolutionAlreadyProposed,
+
+    #[msg("Market has no resolution proposal")]
+    ResolutionNotProposed,
+
+    #[msg("Resolution challenge period has not elapsed")]
+    ChallengePeriodNotElapsed,
+
+    #[msg("Outcome mints of a market must all be soulbound or all transferable")]
+    SoulboundMismatch,
+
+    #[msg("Soulbound outcome tokens can not be migrated")]
+    SoulboundMigration,
+
+    #[msg("Legacy SOL liabilities do not cover the market, schedule the audited total first")]
+    LegacyLiabilitiesNotAudited,
+
+    #[msg("Market was closed and can not be created again")]
+    MarketClosed,
+
+    #[msg("Graduated markets migrate to the amm and can not be resolved")]
+    MarketGraduated,
 }
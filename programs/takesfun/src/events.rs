@@ -1,3 +1,5 @@
+use crate::state::categorical_market::Outcome;
+use crate::state::config::{ConfigUpdate, Role};
 use anchor_lang::prelude::*;
 
 #[event]
@@ -54,3 +56,238 @@ pub struct TradeEvent {
     pub no_virtual_reserve_lamport: u64,
     pub no_virtual_reserve_token: u64,
 }
+
+#[event]
+pub struct CreatorChangedEvent {
+    pub market: Pubkey,
+    pub old_creator: Pubkey,
+    pub new_creator: Pubkey,
+}
+
+#[event]
+pub struct CreatorNominatedEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub pending_creator: Pubkey,
+}
+
+#[event]
+pub struct ConfigUpdatedEvent {
+    pub authority: Pubkey,
+    pub old_value: ConfigUpdate,
+    pub new_value: ConfigUpdate,
+}
+
+#[event]
+pub struct PausedEvent {
+    pub authority: Pubkey,
+    pub old_paused: u8,
+    pub new_paused: u8,
+}
+
+#[event]
+pub struct MarketFrozenEvent {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct MarketLimitsUpdatedEvent {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub max_wallet_sol: u64,
+    pub max_wallet_share_bps: u64,
+}
+
+#[event]
+pub struct MarketVaultMigratedEvent {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub market_vault: Pubkey,
+    //  SOL the market owed, amount falls short of it when the global vault was short
+    pub sol_liabilities: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoleNominatedEvent {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub pending_holder: Pubkey,
+}
+
+#[event]
+pub struct RoleChangedEvent {
+    pub role: Role,
+    pub old_holder: Pubkey,
+    pub new_holder: Pubkey,
+}
+
+#[event]
+pub struct ConfigChangeScheduledEvent {
+    pub id: u64,
+    pub authority: Pubkey,
+    pub execute_after: i64,
+    pub change: ConfigUpdate,
+}
+
+#[event]
+pub struct ConfigChangeCancelledEvent {
+    pub id: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct WhitelistUpdatedEvent {
+    pub user: Pubkey,
+    pub is_allow: u8,
+    pub tier: u8,
+    pub expiry: i64,
+}
+
+#[event]
+pub struct WhitelistRootUpdatedEvent {
+    pub authority: Pubkey,
+    pub old_root: [u8; 32],
+    pub new_root: [u8; 32],
+}
+
+#[event]
+pub struct CompleteEvent {
+    pub market: Pubkey,
+    pub token_type: u8,
+    pub real_sol_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationEvent {
+    pub market: Pubkey,
+    pub amm_program: Pubkey,
+
+    pub yes_pool: Pubkey,
+    pub yes_token_amount: u64,
+    pub yes_sol_amount: u64,
+
+    pub no_pool: Pubkey,
+    pub no_token_amount: u64,
+    pub no_sol_amount: u64,
+
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CategoricalLaunchEvent {
+    pub creator: Pubkey,
+    pub market: Pubkey,
+
+    pub outcomes: Vec<Outcome>,
+
+    pub market_info: String,
+    pub token_supply: u64,
+    pub decimals: u8,
+}
+
+#[event]
+pub struct CategoricalTradeEvent {
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub mint: Pubkey,
+
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub platform_fee_lamports: u64,
+    pub creator_fee_lamports: u64,
+    pub shift_lamports_real: u64,
+    pub shift_lamports_virtual: u64,
+
+    pub direction: u8,
+    pub outcome: u8,
+    pub timestamp: i64,
+
+    pub outcomes: Vec<Outcome>,
+}
+
+#[event]
+pub struct ResolutionProposedEvent {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub proposed_value: i64,
+    pub challenge_deadline: i64,
+}
+
+#[event]
+pub struct ResolutionChallengedEvent {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub proposed_value: i64,
+}
+
+#[event]
+pub struct ResolvedEvent {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub resolved_value: i64,
+
+    pub yes_payout_pool: u64,
+    pub no_payout_pool: u64,
+    pub yes_outstanding: u64,
+    pub no_outstanding: u64,
+}
+
+#[event]
+pub struct MarketMetadataEvent {
+    pub authority: Pubkey,
+    pub market: Pubkey,
+    pub question: String,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub resolution_source: String,
+    pub resolution_criteria: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketClosedEvent {
+    pub market: Pubkey,
+    pub payer: Pubkey,
+    //  unredeemed collateral and dust swept to the team wallet
+    pub unredeemed_amount: u64,
+    pub dust_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemEvent {
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub token_type: u8,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+}
+
+#[event]
+pub struct AuditEvent {
+    pub market: Pubkey,
+    pub collateral_mint: Pubkey,
+
+    pub real_yes_sol_reserves: u64,
+    pub real_no_sol_reserves: u64,
+    pub sol_liabilities: u64,
+    pub vault_balance: u64,
+
+    pub real_yes_token_reserves: u64,
+    pub yes_token_balance: u64,
+    pub real_no_token_reserves: u64,
+    pub no_token_balance: u64,
+
+    //  the global vault still holds the SOL of legacy markets
+    pub legacy_sol_liabilities: u64,
+    pub global_vault_balance: u64,
+    //  Config::total_sol_liabilities after this market reported its liabilities
+    pub total_sol_liabilities: u64,
+
+    pub solvent: bool,
+    pub timestamp: i64,
+}
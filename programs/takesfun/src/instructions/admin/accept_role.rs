@@ -0,0 +1,36 @@
+use constants::CONFIG;
+use errors::TakesFunError;
+use events::RoleChangedEvent;
+
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(role: Role)]
+pub struct AcceptRole<'info> {
+    //  Pending role holder
+    #[account(
+        constraint = global_config.pending_role(role) == new_holder.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub new_holder: Signer<'info>,
+
+    //  Stores role addresses
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+}
+
+impl AcceptRole<'_> {
+    pub fn process(&mut self, role: Role) -> Result<()> {
+        let old_holder = self.global_config.accept_role(role);
+
+        emit!(RoleChangedEvent {
+            role,
+            old_holder,
+            new_holder: self.new_holder.key(),
+        });
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use constants::{CONFIG, WHITELIST};
 use crate::state::whitelist::*;
 
 use crate::errors::*;
+use crate::events::WhitelistUpdatedEvent;
 
 #[derive(Accounts)]
 #[instruction(new_creator: Pubkey)]
@@ -19,14 +20,14 @@ pub struct AddWl<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + std::mem::size_of::<Whitelist>(),
+        space = 8 + Whitelist::INIT_SPACE,
         seeds = [WHITELIST.as_bytes(), &user.key().to_bytes()],
         bump
     )]
     pub whitelist: Account<'info, Whitelist>,
     #[account(
         mut,
-        constraint = admin.key() == global_config.backend_sign_authority.key() @ TakesFunError::IncorrectAuthority
+        constraint = admin.key() == global_config.whitelist_manager @ TakesFunError::IncorrectAuthority
     )]
     pub admin: Signer<'info>,
     #[account(mut,
@@ -37,13 +38,16 @@ pub struct AddWl<'info> {
 }
 
 impl<'info> AddWl<'info> {
-    pub fn handler(&mut self, new_whitelister: Pubkey) -> Result<()> {
+    pub fn handler(&mut self, new_whitelister: Pubkey, tier: u8, expiry: i64) -> Result<()> {
         let whitelist = &mut self.whitelist;
-        if whitelist.is_allow == 0 {
-            whitelist.creator = new_whitelister.key();
-            whitelist.is_allow = 1;
-            whitelist.first_swap_timestamp = 0;
-        }
+        whitelist.add(new_whitelister, tier, expiry)?;
+
+        emit!(WhitelistUpdatedEvent {
+            user: new_whitelister,
+            is_allow: whitelist.is_allow,
+            tier,
+            expiry,
+        });
         Ok(())
     }
 }
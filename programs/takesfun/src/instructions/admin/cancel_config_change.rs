@@ -0,0 +1,40 @@
+use constants::{CONFIG, CONFIG_CHANGE};
+use errors::TakesFunError;
+use events::ConfigChangeCancelledEvent;
+use state::config_change::ConfigChange;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    // Current admin
+    #[account(
+        mut,
+        constraint = global_config.authority == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [CONFIG_CHANGE.as_bytes(), &config_change.id.to_le_bytes()],
+        bump
+    )]
+    config_change: Box<Account<'info, ConfigChange>>,
+}
+
+impl CancelConfigChange<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        emit!(ConfigChangeCancelledEvent {
+            id: self.config_change.id,
+            authority: self.admin.key(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+use constants::WHITELIST;
+
+use crate::state::whitelist::*;
+
+use crate::errors::*;
+use crate::utils::close_program_account;
+
+#[derive(Accounts)]
+pub struct CloseWl<'info> {
+    //  inactive entries can be closed by anyone, rent goes back to the user who paid it,
+    //  revoked entries stay so the revocation can not be undone by recreating the entry
+    /// CHECK: may still have a legacy layout, deserialized inside the instruction
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [WHITELIST.as_bytes(), &user.key().to_bytes()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// CHECK: owner of the whitelist entry, receives the rent
+    #[account(mut)]
+    pub user: AccountInfo<'info>,
+}
+
+impl<'info> CloseWl<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        //  checks the discriminator
+        let whitelist = Whitelist::try_deserialize_any_layout(&self.whitelist.try_borrow_data()?)?;
+        require!(
+            whitelist.is_allow == 0 && !whitelist.revoked,
+            TakesFunError::WhitelistStillActive
+        );
+
+        msg!("close whitelist of {}", self.user.key());
+        close_program_account(
+            self.whitelist.to_account_info(),
+            self.user.to_account_info(),
+        )
+    }
+}
@@ -6,7 +6,6 @@ use crate::{
 };
 use anchor_lang::{prelude::*, system_program, Discriminator};
 use anchor_spl::{associated_token::AssociatedToken, token::Token};
-use borsh::BorshDeserialize;
 
 #[derive(Accounts)]
 pub struct Configure<'info> {
@@ -38,55 +37,42 @@ pub struct Configure<'info> {
 }
 
 impl<'info> Configure<'info> {
-    pub fn handler(&mut self, new_config: Config, config_bump: u8) -> Result<()> {
-        msg!("Configure 3");
+    pub fn handler(&mut self, mut new_config: Config, config_bump: u8) -> Result<()> {
+        //  config can only be created once, changes go through schedule_config_change and
+        //  execute_config_change after the timelock
+        if self.config.owner == &crate::ID {
+            return err!(TakesFunError::ConfigAlreadyInitialized);
+        }
+
+        //  roles can only be handed over with the nominate / accept instructions
+        new_config.pending_authority = Pubkey::default();
+        new_config.pending_guardian = Pubkey::default();
+        new_config.pending_curator = Pubkey::default();
+        new_config.pending_whitelist_manager = Pubkey::default();
+        new_config.initialized = true;
+        new_config.legacy_sol_liabilities = 0;
+        new_config.total_sol_liabilities = 0;
+        new_config.validate()?;
+
         let serialized_config =
             [&Config::DISCRIMINATOR, new_config.try_to_vec()?.as_slice()].concat();
         let serialized_config_len = serialized_config.len();
         let config_cost = Rent::get()?.minimum_balance(serialized_config_len);
 
-        msg!("Configure 1");
         //  init config pda
-        if self.config.owner != &crate::ID {
-            let cpi_context = CpiContext::new(
-                self.system_program.to_account_info(),
-                system_program::CreateAccount {
-                    from: self.payer.to_account_info(),
-                    to: self.config.to_account_info(),
-                },
-            );
-            system_program::create_account(
-                cpi_context.with_signer(&[&[CONFIG.as_bytes(), &[config_bump]]]),
-                config_cost,
-                serialized_config_len as u64,
-                &crate::ID,
-            )?;
-        } else {
-            let data = self.config.try_borrow_data()?;
-            if data.len() < 8 || &data[0..8] != Config::DISCRIMINATOR {
-                return err!(TakesFunError::IncorrectConfigAccount);
-            }
-            let config = Config::deserialize(&mut &data[8..])?;
-
-            if config.authority != self.payer.key() {
-                return err!(TakesFunError::IncorrectAuthority);
-            }
-        }
-        msg!("Configure 2");
-        let lamport_delta = (config_cost as i64) - (self.config.lamports() as i64);
-        if lamport_delta > 0 {
-            system_program::transfer(
-                CpiContext::new(
-                    self.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: self.payer.to_account_info(),
-                        to: self.config.to_account_info(),
-                    },
-                ),
-                lamport_delta as u64,
-            )?;
-            self.config.realloc(serialized_config_len, false)?;
-        }
+        let cpi_context = CpiContext::new(
+            self.system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: self.payer.to_account_info(),
+                to: self.config.to_account_info(),
+            },
+        );
+        system_program::create_account(
+            cpi_context.with_signer(&[&[CONFIG.as_bytes(), &[config_bump]]]),
+            config_cost,
+            serialized_config_len as u64,
+            &crate::ID,
+        )?;
 
         (self.config.try_borrow_mut_data()?[..serialized_config_len])
             .copy_from_slice(serialized_config.as_slice());
@@ -0,0 +1,57 @@
+use constants::{CONFIG, CONFIG_CHANGE};
+use errors::TakesFunError;
+use events::ConfigUpdatedEvent;
+use state::config_change::ConfigChange;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    //  anyone can execute a change once its timelock has passed
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [CONFIG_CHANGE.as_bytes(), &config_change.id.to_le_bytes()],
+        bump
+    )]
+    config_change: Box<Account<'info, ConfigChange>>,
+
+    /// CHECK: receives the rent of the config change, should be the one who scheduled it
+    #[account(
+        mut,
+        constraint = config_change.authority == authority.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub authority: AccountInfo<'info>,
+}
+
+impl ExecuteConfigChange<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        self.config_change.check_executable(current_timestamp)?;
+
+        let update = self.config_change.change.clone();
+        msg!(
+            "execute config change {}: {:#?}",
+            self.config_change.id,
+            update
+        );
+        let old_value = self.global_config.apply_update(update.clone())?;
+        self.global_config.validate()?;
+
+        emit!(ConfigUpdatedEvent {
+            authority: self.config_change.authority,
+            old_value,
+            new_value: update,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::errors::*;
+use crate::{constants::CONFIG, state::config::*, utils::sol_transfer_from_user};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    /// CHECK: config with an older layout, deserialized after the realloc
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    config: AccountInfo<'info>,
+
+    #[account(address = system_program::ID)]
+    system_program: Program<'info, System>,
+}
+
+impl MigrateConfig<'_> {
+    pub fn process(&mut self, timelock_delay: i64) -> Result<()> {
+        let new_len = 8 + Config::INIT_SPACE;
+        require!(
+            self.config.data_len() < new_len,
+            TakesFunError::ConfigAlreadyMigrated
+        );
+
+        //  older layouts are a prefix of the current one, the new fields deserialize
+        //  from the zeroed tail
+        let mut data = self.config.try_borrow_data()?.to_vec();
+        data.resize(new_len, 0);
+        let mut config = Config::try_deserialize(&mut data.as_slice())?;
+        require!(
+            self.authority.key() == config.authority,
+            TakesFunError::IncorrectAuthority
+        );
+        config.upgrade(timelock_delay)?;
+
+        let rent = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(self.config.lamports());
+        if rent > 0 {
+            sol_transfer_from_user(
+                &self.authority,
+                self.config.clone(),
+                &self.system_program,
+                rent,
+            )?;
+        }
+        self.config.realloc(new_len, true)?;
+        config.try_serialize(&mut &mut self.config.try_borrow_mut_data()?[..])?;
+        msg!("Config migration complete: {:#?}", config);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use constants::{CONFIG, GLOBAL, MARKET_VAULT};
+use errors::TakesFunError;
+use events::MarketVaultMigratedEvent;
+use state::market::Market;
+use utils::move_legacy_sol;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MigrateMarketVault<'info> {
+    // Current admin or curator
+    #[account(
+        mut,
+        constraint = global_config.authority == *admin.key
+            || global_config.curator == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    market: Box<Account<'info, Market>>,
+
+    /// CHECK: global vault pda which held the SOL of legacy markets
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateMarketVault<'_> {
+    pub fn process(&mut self, global_vault_bump: u8) -> Result<()> {
+        require!(
+            !self.market.has_market_vault,
+            TakesFunError::MarketVaultAlreadyMigrated
+        );
+        require!(
+            self.market.is_sol_collateral(),
+            TakesFunError::UnsupportedCollateral
+        );
+
+        //  everything the market still owes its traders moves out of the shared vault, capped
+        //  by its share of what the vault actually holds
+        let sol_liabilities = self
+            .market
+            .sol_liabilities()
+            .ok_or(TakesFunError::ArithmeticError)?;
+        let amount = move_legacy_sol(
+            &mut self.global_config,
+            &self.admin,
+            self.global_vault.clone(),
+            self.market_vault.clone(),
+            &self.system_program,
+            global_vault_bump,
+            sol_liabilities,
+        )?;
+        self.market
+            .write_down_sol_liabilities(amount, sol_liabilities)?;
+        self.market.has_market_vault = true;
+        let written_down = self
+            .market
+            .sol_liabilities()
+            .ok_or(TakesFunError::ArithmeticError)?;
+        self.global_config
+            .reconcile_sol_liabilities(&mut self.market.reported_sol_liabilities, written_down)?;
+
+        emit!(MarketVaultMigratedEvent {
+            authority: self.admin.key(),
+            market: self.market.key(),
+            market_vault: self.market_vault.key(),
+            sol_liabilities,
+            amount,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+use constants::WHITELIST;
+
+use crate::state::whitelist::*;
+
+use crate::utils::sol_transfer_from_user;
+use anchor_lang::system_program;
+
+#[derive(Accounts)]
+pub struct MigrateWhitelist<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: whitelist entry with an older layout, deserialized after the realloc
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [WHITELIST.as_bytes(), &user.key().to_bytes()],
+        bump,
+    )]
+    pub whitelist: AccountInfo<'info>,
+
+    /// CHECK: owner of the whitelist entry, only used for the seeds
+    pub user: AccountInfo<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateWhitelist<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        //  older layouts are shorter, the new fields deserialize from the zeroed tail,
+        //  which keeps legacy entries on tier 0 without expiry
+        let new_len = 8 + Whitelist::INIT_SPACE;
+        if self.whitelist.data_len() < new_len {
+            let rent = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(self.whitelist.lamports());
+            if rent > 0 {
+                sol_transfer_from_user(
+                    &self.payer,
+                    self.whitelist.clone(),
+                    &self.system_program,
+                    rent,
+                )?;
+            }
+            self.whitelist.realloc(new_len, true)?;
+        }
+
+        let whitelist = Whitelist::try_deserialize(&mut &self.whitelist.try_borrow_data()?[..])?;
+        msg!("Whitelist migration complete: {:?}", whitelist);
+
+        Ok(())
+    }
+}
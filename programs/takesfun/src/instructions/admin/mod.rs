@@ -1,4 +1,19 @@
 pub mod accept_authority;
+pub mod accept_role;
 pub mod add_wl;
+pub mod cancel_config_change;
+pub mod close_wl;
 pub mod configure;
+pub mod execute_config_change;
+pub mod migrate_categorical_market_vault;
+pub mod migrate_config;
+pub mod migrate_market_vault;
+pub mod migrate_whitelist;
 pub mod nominate_authority;
+pub mod nominate_role;
+pub mod remove_wl;
+pub mod schedule_config_change;
+pub mod set_market_frozen;
+pub mod set_market_limits;
+pub mod set_paused;
+pub mod set_whitelist_root;
@@ -0,0 +1,35 @@
+use constants::CONFIG;
+use errors::TakesFunError;
+use events::RoleNominatedEvent;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct NominateRole<'info> {
+    // Current admin
+    #[account(
+        constraint = global_config.authority == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    //  Stores role addresses
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+}
+
+impl NominateRole<'_> {
+    pub fn process(&mut self, role: Role, new_holder: Pubkey) -> Result<()> {
+        self.global_config.set_pending_role(role, new_holder);
+
+        emit!(RoleNominatedEvent {
+            role,
+            holder: self.global_config.role(role),
+            pending_holder: new_holder,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use crate::*;
+
+use constants::{CONFIG, WHITELIST};
+
+use crate::state::whitelist::*;
+
+use crate::errors::*;
+use crate::events::WhitelistUpdatedEvent;
+
+#[derive(Accounts)]
+#[instruction(whitelister: Pubkey)]
+pub struct RemoveWl<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [WHITELIST.as_bytes(), &whitelister.to_bytes()],
+        bump
+    )]
+    pub whitelist: Account<'info, Whitelist>,
+
+    #[account(
+        constraint = admin.key() == global_config.whitelist_manager @ TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+}
+
+impl<'info> RemoveWl<'info> {
+    pub fn handler(&mut self, whitelister: Pubkey) -> Result<()> {
+        let whitelist = &mut self.whitelist;
+        whitelist.revoke();
+
+        emit!(WhitelistUpdatedEvent {
+            user: whitelister,
+            is_allow: whitelist.is_allow,
+            tier: whitelist.tier,
+            expiry: whitelist.expiry,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use constants::{CONFIG, CONFIG_CHANGE};
+use errors::TakesFunError;
+use events::ConfigChangeScheduledEvent;
+use state::config_change::ConfigChange;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ScheduleConfigChange<'info> {
+    // Current admin
+    #[account(
+        mut,
+        constraint = global_config.authority == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ConfigChange::INIT_SPACE,
+        seeds = [CONFIG_CHANGE.as_bytes(), &global_config.config_change_nonce.to_le_bytes()],
+        bump
+    )]
+    config_change: Box<Account<'info, ConfigChange>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl ScheduleConfigChange<'_> {
+    pub fn process(&mut self, change: ConfigUpdate, execute_after: i64) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        ConfigChange::check_execute_after(&self.global_config, execute_after, current_timestamp)?;
+
+        //  reject changes that could never be executed
+        let mut preview = (**self.global_config).clone();
+        preview.apply_update(change.clone())?;
+        preview.validate()?;
+
+        let config_change = &mut self.config_change;
+        config_change.id = self.global_config.config_change_nonce;
+        config_change.authority = self.admin.key();
+        config_change.execute_after = execute_after;
+        config_change.change = change;
+
+        self.global_config.config_change_nonce += 1;
+
+        emit!(ConfigChangeScheduledEvent {
+            id: config_change.id,
+            authority: config_change.authority,
+            execute_after,
+            change: config_change.change.clone(),
+        });
+        Ok(())
+    }
+}
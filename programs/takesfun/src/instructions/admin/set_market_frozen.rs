@@ -0,0 +1,42 @@
+use constants::CONFIG;
+use errors::TakesFunError;
+use events::MarketFrozenEvent;
+use state::market::Market;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetMarketFrozen<'info> {
+    // Current admin or guardian
+    #[account(
+        constraint = global_config.authority == *admin.key
+            || global_config.guardian == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    market: Account<'info, Market>,
+}
+
+impl SetMarketFrozen<'_> {
+    pub fn process(&mut self, frozen: bool) -> Result<()> {
+        //  guardian can only freeze
+        if self.admin.key() != self.global_config.authority {
+            require!(frozen, TakesFunError::IncorrectAuthority);
+        }
+        self.market.frozen = frozen;
+
+        emit!(MarketFrozenEvent {
+            authority: self.admin.key(),
+            market: self.market.key(),
+            frozen,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use constants::CONFIG;
+use errors::TakesFunError;
+use events::MarketLimitsUpdatedEvent;
+use state::market::Market;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetMarketLimits<'info> {
+    // Current admin or curator
+    #[account(
+        constraint = global_config.authority == *admin.key
+            || global_config.curator == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    market: Account<'info, Market>,
+}
+
+impl SetMarketLimits<'_> {
+    pub fn process(&mut self, max_wallet_sol: u64, max_wallet_share_bps: u64) -> Result<()> {
+        require!(max_wallet_share_bps <= 10_000, TakesFunError::ValueTooLarge);
+        self.market.max_wallet_sol = max_wallet_sol;
+        self.market.max_wallet_share_bps = max_wallet_share_bps;
+
+        emit!(MarketLimitsUpdatedEvent {
+            authority: self.admin.key(),
+            market: self.market.key(),
+            max_wallet_sol,
+            max_wallet_share_bps,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use constants::CONFIG;
+use errors::TakesFunError;
+use events::PausedEvent;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    // Current admin or guardian
+    #[account(
+        constraint = global_config.authority == *admin.key
+            || global_config.guardian == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+}
+
+impl SetPaused<'_> {
+    pub fn process(&mut self, paused: u8) -> Result<()> {
+        let old_paused = self.global_config.paused;
+        //  guardian can only add pause flags
+        if self.admin.key() != self.global_config.authority {
+            require!(
+                paused & old_paused == old_paused,
+                TakesFunError::IncorrectAuthority
+            );
+        }
+        //  only the bitmask changes, a stale field elsewhere must not block an emergency stop
+        require!(
+            paused & !Config::PAUSE_ALL == 0,
+            TakesFunError::ValueInvalid
+        );
+        self.global_config.paused = paused;
+
+        emit!(PausedEvent {
+            authority: self.admin.key(),
+            old_paused,
+            new_paused: paused,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use constants::CONFIG;
+use errors::TakesFunError;
+use events::WhitelistRootUpdatedEvent;
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetWhitelistRoot<'info> {
+    // Current admin
+    #[account(
+        constraint = global_config.authority == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+}
+
+impl SetWhitelistRoot<'_> {
+    pub fn process(&mut self, root: [u8; 32]) -> Result<()> {
+        let old_root = self.global_config.whitelist_merkle_root;
+        self.global_config.whitelist_merkle_root = root;
+
+        emit!(WhitelistRootUpdatedEvent {
+            authority: self.admin.key(),
+            old_root,
+            new_root: root,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{errors::TakesFunError, events::CreatorChangedEvent, state::market::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptCreator<'info> {
+    //  Pending creator
+    #[account(
+        constraint = market.pending_creator == new_creator.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub new_creator: Signer<'info>,
+
+    //  Stores creator address
+    #[account(mut)]
+    market: Account<'info, Market>,
+}
+
+impl AcceptCreator<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let old_creator = self.market.creator;
+        self.market.creator = self.new_creator.key();
+        self.market.pending_creator = Pubkey::default();
+
+        emit!(CreatorChangedEvent {
+            market: self.market.key(),
+            old_creator,
+            new_creator: self.market.creator,
+        });
+        Ok(())
+    }
+}
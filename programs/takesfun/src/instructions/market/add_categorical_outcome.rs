@@ -0,0 +1,133 @@
+use crate::{
+    constants::{CONFIG, GLOBAL},
+    errors::*,
+    events::CategoricalLaunchEvent,
+    state::{categorical_market::*, config::*, curve::Curve},
+    utils::*,
+};
+use anchor_lang::{prelude::*, solana_program::sysvar::SysvarId, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::TokenInterface,
+};
+
+#[derive(Accounts)]
+pub struct AddCategoricalOutcome<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// CHECK: global vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = market.creator == creator.key() @TakesFunError::IncorrectAuthority
+    )]
+    market: Box<Account<'info, CategoricalMarket>>,
+
+    //  created in instruction under token_program
+    #[account(mut)]
+    outcome_token: Signer<'info>,
+
+    /// CHECK: created in instruction
+    #[account(
+        mut,
+        seeds = [
+            global_vault.key().as_ref(),
+            token_program.key().as_ref(),
+            outcome_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    global_outcome_token_account: UncheckedAccount<'info>,
+
+    #[account(address = system_program::ID)]
+    system_program: Program<'info, System>,
+    #[account(address = Rent::id())]
+    rent: Sysvar<'info, Rent>,
+    token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> AddCategoricalOutcome<'info> {
+    pub fn handler(
+        &mut self,
+
+        // metadata
+        name: String,
+        symbol: String,
+        uri: String,
+
+        global_vault_bump: u8,
+    ) -> Result<()> {
+        let global_config = &self.global_config;
+        require!(
+            !global_config.is_paused(Config::PAUSE_CREATE),
+            TakesFunError::ProgramPaused
+        );
+        require!(!self.market.is_ready(), TakesFunError::InvalidOutcome);
+
+        let global_vault = &self.global_vault;
+        let outcome_token = &self.outcome_token;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        //  create the mint and its metadata, mint outcome tokens to market
+        create_outcome_mint(
+            CreateOutcomeMint {
+                mint: outcome_token.to_account_info(),
+                payer: self.creator.to_account_info(),
+                global_vault: global_vault.to_account_info(),
+                global_token_account: self.global_outcome_token_account.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            signer_seeds,
+            global_config.token_decimals_config,
+            global_config.token_supply_config,
+            self.market.soulbound,
+            name,
+            symbol,
+            uri,
+        )?;
+
+        //  every outcome starts on the yes curve of the config
+        let market = &mut self.market;
+        market.outcomes.push(Outcome {
+            mint: outcome_token.key(),
+            curve: Curve {
+                virtual_sol_reserves: global_config.initial_virtual_yes_sol_reserves_config,
+                virtual_token_reserves: global_config.initial_virtual_yes_token_reserves_config,
+                real_sol_reserves: 0,
+                real_token_reserves: global_config.initial_real_yes_token_reserves_config,
+            },
+        });
+
+        if market.is_ready() {
+            emit!(CategoricalLaunchEvent {
+                creator: market.creator,
+                market: market.key(),
+                outcomes: market.outcomes.clone(),
+                market_info: market.market_info.clone(),
+                token_supply: global_config.token_supply_config,
+                decimals: global_config.token_decimals_config,
+            });
+        }
+
+        Ok(())
+    }
+}
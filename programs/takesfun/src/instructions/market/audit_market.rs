@@ -0,0 +1,166 @@
+use crate::{
+    constants::{CONFIG, GLOBAL, MARKET_VAULT},
+    errors::*,
+    events::AuditEvent,
+    state::{config::*, market::*},
+    utils::is_soulbound,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct AuditMarket<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        constraint = market.yes_token_mint == yes_token.key() @TakesFunError::IncorrectYesToken,
+        constraint = market.no_token_mint == no_token.key() @TakesFunError::IncorrectNoToken
+    )]
+    market: Box<Account<'info, Market>>,
+
+    /// CHECK: global vault pda which owns the outcome and collateral atas
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &global_vault.key(),
+            &yes_token.key(),
+            yes_token.to_account_info().owner
+        )
+    )]
+    global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &global_vault.key(),
+            &no_token.key(),
+            no_token.to_account_info().owner
+        )
+    )]
+    global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    //  required for spl collateral markets
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &global_vault.key(),
+            &market.collateral_mint,
+            global_collateral_ata.to_account_info().owner
+        )
+    )]
+    global_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+impl AuditMarket<'_> {
+    pub fn handler(&mut self) -> Result<()> {
+        let rent = Rent::get()?;
+
+        let sol_liabilities = self
+            .market
+            .sol_liabilities()
+            .ok_or(TakesFunError::ArithmeticError)?;
+        if self.market.is_sol_collateral() {
+            self.global_config.reconcile_sol_liabilities(
+                &mut self.market.reported_sol_liabilities,
+                sol_liabilities,
+            )?;
+        }
+        let market = &self.market;
+        let global_vault_balance = self
+            .global_vault
+            .lamports()
+            .saturating_sub(rent.minimum_balance(self.global_vault.data_len()));
+
+        //  holdings backing the liabilities, legacy markets still share the global vault and
+        //  are checked together against the audited legacy total. every market of an spl
+        //  collateral shares its global ata, one market can not tell whether it is short, so
+        //  the collateral balance is reported but left out of the solvency flag
+        let legacy_sol_liabilities = self.global_config.legacy_sol_liabilities;
+        let (vault_balance, backed_liabilities) = if !market.is_sol_collateral() {
+            let balance = self
+                .global_collateral_ata
+                .as_ref()
+                .ok_or(TakesFunError::MissingCollateralAccount)?
+                .amount;
+            (balance, 0)
+        } else if market.has_market_vault {
+            let balance = self
+                .market_vault
+                .lamports()
+                .saturating_sub(rent.minimum_balance(0));
+            (balance, sol_liabilities)
+        } else {
+            (
+                global_vault_balance,
+                legacy_sol_liabilities.max(sol_liabilities),
+            )
+        };
+
+        let yes_token_balance = self.global_yes_ata.amount;
+        let no_token_balance = self.global_no_ata.amount;
+
+        //  soulbound tokens are minted on demand, their global atas hold nothing
+        let solvent = vault_balance >= backed_liabilities
+            && (is_soulbound(&self.yes_token.to_account_info())?
+                || yes_token_balance >= market.real_yes_token_reserves)
+            && (is_soulbound(&self.no_token.to_account_info())?
+                || no_token_balance >= market.real_no_token_reserves);
+        if !solvent {
+            msg!(
+                "audit mismatch :: liabilities: {} vault: {} yes: {}/{} no: {}/{}",
+                backed_liabilities,
+                vault_balance,
+                market.real_yes_token_reserves,
+                yes_token_balance,
+                market.real_no_token_reserves,
+                no_token_balance
+            );
+        }
+
+        emit!(AuditEvent {
+            market: market.key(),
+            collateral_mint: market.collateral_mint,
+
+            real_yes_sol_reserves: market.real_yes_sol_reserves,
+            real_no_sol_reserves: market.real_no_sol_reserves,
+            sol_liabilities,
+            vault_balance,
+
+            real_yes_token_reserves: market.real_yes_token_reserves,
+            yes_token_balance,
+            real_no_token_reserves: market.real_no_token_reserves,
+            no_token_balance,
+
+            legacy_sol_liabilities,
+            global_vault_balance,
+            total_sol_liabilities: self.global_config.total_sol_liabilities,
+
+            solvent,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
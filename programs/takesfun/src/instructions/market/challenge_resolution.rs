@@ -0,0 +1,41 @@
+use crate::{
+    constants::CONFIG,
+    errors::*,
+    events::ResolutionChallengedEvent,
+    state::{config::*, market::*},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ChallengeResolution<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    // Current admin or guardian
+    #[account(
+        constraint = global_config.authority == *admin.key
+            || global_config.guardian == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    market: Box<Account<'info, Market>>,
+}
+
+impl ChallengeResolution<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let proposed_value = market.proposed_value;
+        market.challenge_resolution()?;
+
+        emit!(ResolutionChallengedEvent {
+            authority: self.admin.key(),
+            market: market.key(),
+            proposed_value,
+        });
+        Ok(())
+    }
+}
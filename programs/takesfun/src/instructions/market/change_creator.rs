@@ -1,11 +1,11 @@
 use crate::{
-    constants::{CONFIG, MARKET, },
+    constants::{CONFIG, MARKET},
     errors::*,
+    events::CreatorChangedEvent,
     state::{config::*, market::*},
     utils::*,
 };
-use anchor_lang::{prelude::*,};
-use anchor_spl::token::Mint;
+use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 #[instruction(market_info: String)]
@@ -17,19 +17,12 @@ pub struct ChangeCreator<'info> {
     )]
     global_config: Box<Account<'info, Config>>,
 
-    #[account(mut)]
-    creator: Signer<'info>,
-
     #[account(
         mut,
-        constraint = admin.key() == global_config.backend_sign_authority.key() @ TakesFunError::IncorrectAuthority
+        constraint = admin.key() == global_config.curator @ TakesFunError::IncorrectAuthority
     )]
     pub admin: Signer<'info>,
 
-    pub yes_token: Box<Account<'info, Mint>>,
-
-    pub no_token: Box<Account<'info, Mint>>,
-
     #[account(
         mut,
         seeds = [MARKET.as_bytes(), &market_info.to_hashed_bytes()], 
@@ -39,11 +32,17 @@ pub struct ChangeCreator<'info> {
 }
 
 impl<'info> ChangeCreator<'info> {
-    pub fn handler(&mut self, market_info: String, new_creator: Pubkey) -> Result<()> {
+    pub fn handler(&mut self, _market_info: String, new_creator: Pubkey) -> Result<()> {
         let market = &mut self.market;
-        market.creator = new_creator;   
+        let old_creator = market.creator;
+        market.creator = new_creator;
+        market.pending_creator = Pubkey::default();
+
+        emit!(CreatorChangedEvent {
+            market: market.key(),
+            old_creator,
+            new_creator,
+        });
         Ok(())
     }
 }
-
-
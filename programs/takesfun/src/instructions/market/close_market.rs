@@ -0,0 +1,357 @@
+use crate::{
+    constants::{
+        CLOSE_GRACE_PERIOD, CONFIG, CREATOR, GLOBAL, MARKET_METADATA, MARKET_TOMBSTONE,
+        MARKET_VAULT,
+    },
+    errors::*,
+    events::MarketClosedEvent,
+    state::{config::*, market::*, market_metadata::*, market_tombstone::*},
+    utils::*,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    //  pays the rent of the tombstone
+    #[account(mut)]
+    pub closer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        constraint = market.yes_token_mint == yes_token.key() @TakesFunError::IncorrectYesToken,
+        constraint = market.no_token_mint == no_token.key() @TakesFunError::IncorrectNoToken
+    )]
+    market: Box<Account<'info, Market>>,
+
+    //  keeps markets seeded by their question from being created again at this address
+    #[account(
+        init,
+        payer = closer,
+        space = 8 + MarketTombstone::INIT_SPACE,
+        seeds = [MARKET_TOMBSTONE.as_bytes(), &market.key().to_bytes()],
+        bump
+    )]
+    pub market_tombstone: Box<Account<'info, MarketTombstone>>,
+
+    //  only exists once update_market_metadata was called
+    #[account(
+        mut,
+        close = payer,
+        seeds = [MARKET_METADATA.as_bytes(), &market.key().to_bytes()],
+        bump
+    )]
+    pub market_metadata: Option<Box<Account<'info, MarketMetadata>>>,
+
+    /// CHECK: paid for the market creation, receives the rent back
+    #[account(mut, address = market.payer @TakesFunError::IncorrectAuthority)]
+    pub payer: AccountInfo<'info>,
+
+    /// CHECK: should be same with the address in the global_config
+    #[account(
+        mut,
+        constraint = global_config.team_wallet == team_wallet.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub team_wallet: AccountInfo<'info>,
+
+    /// CHECK: receives the unclaimed fees of the creator vault
+    #[account(mut, address = market.creator @TakesFunError::IncorrectAuthority)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: CREATOR vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub creator_vault: AccountInfo<'info>,
+
+    /// CHECK: global vault pda which owns the outcome and collateral atas
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+        associated_token::token_program = token_program,
+    )]
+    global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    //  spl collateral accounts, only passed for markets whose collateral is not native sol
+    pub collateral_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = global_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub global_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = team_wallet,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub team_wallet_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    //  holds the creator fees of spl collateral markets, swept to the creator and closed
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = creator_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_vault_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = closer,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub collateral_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CloseMarket<'info> {
+    pub fn handler(
+        &mut self,
+        global_vault_bump: u8,
+        market_vault_bump: u8,
+        creator_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            !self.global_config.is_paused(Config::PAUSE_CLAIM),
+            TakesFunError::ProgramPaused
+        );
+        require!(!self.market.frozen, TakesFunError::MarketFrozen);
+        require!(
+            self.market.has_market_vault,
+            TakesFunError::MarketVaultNotMigrated
+        );
+
+        //  migrated markets trade in the amm, resolved ones once every winning token is
+        //  redeemed or the grace period is over
+        let timestamp = Clock::get()?.unix_timestamp;
+        let unredeemed_amount = self
+            .market
+            .sol_liabilities()
+            .ok_or(TakesFunError::ArithmeticError)?;
+        let closable = if self.market.resolved {
+            unredeemed_amount == 0
+                || timestamp >= self.market.resolved_at.saturating_add(CLOSE_GRACE_PERIOD)
+        } else {
+            self.market.is_completed
+        };
+        require!(closable, TakesFunError::MarketNotClosable);
+
+        //  whatever is left unredeemed is swept below, the market leaves the total
+        self.global_config
+            .reconcile_sol_liabilities(&mut self.market.reported_sol_liabilities, 0)?;
+
+        let market_key = self.market.key();
+        self.market_tombstone.market = market_key;
+        self.market_tombstone.closed_at = timestamp;
+        let global_vault_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+        let market_vault_seeds: &[&[&[u8]]] = &[&[
+            MARKET_VAULT.as_bytes(),
+            market_key.as_ref(),
+            &[market_vault_bump],
+        ]];
+
+        //  unredeemed spl collateral left after the grace period
+        if !self.market.is_sol_collateral() && unredeemed_amount > 0 {
+            let collateral = Collateral::new(
+                self.market.collateral_mint,
+                Vault {
+                    account: self.market_vault.to_account_info(),
+                    signer_seeds: market_vault_seeds,
+                },
+                Vault {
+                    account: self.global_vault.to_account_info(),
+                    signer_seeds: global_vault_seeds,
+                },
+                &self.system_program,
+                self.collateral_token(),
+            )?;
+            let team_wallet = collateral.account(
+                &self.team_wallet,
+                self.team_wallet_collateral_ata
+                    .as_ref()
+                    .map(|ata| ata.to_account_info()),
+            )?;
+            collateral.transfer_from_vault(team_wallet, unredeemed_amount)?;
+        }
+
+        //  rounding dust, unredeemed SOL and the rent of the market vault
+        let dust_lamports = self.market_vault.lamports();
+        if dust_lamports > 0 {
+            sol_transfer_with_signer(
+                self.market_vault.clone(),
+                self.team_wallet.clone(),
+                &self.system_program,
+                market_vault_seeds,
+                dust_lamports,
+            )?;
+        }
+
+        //  unclaimed creator fees go to the creator before the vault is emptied
+        let creator_vault_seeds: &[&[&[u8]]] = &[&[
+            CREATOR.as_bytes(),
+            market_key.as_ref(),
+            &[creator_vault_bump],
+        ]];
+        if !self.market.is_sol_collateral() {
+            self.sweep_creator_collateral(creator_vault_seeds)?;
+        }
+        let creator_lamports = self.creator_vault.lamports();
+        if creator_lamports > 0 {
+            sol_transfer_with_signer(
+                self.creator_vault.clone(),
+                self.creator.clone(),
+                &self.system_program,
+                creator_vault_seeds,
+                creator_lamports,
+            )?;
+        }
+
+        //  the global atas only hold tokens nobody can buy anymore, burn them and
+        //  refund the ata rent, mints and metadata have no close authority and stay
+        for (mint, ata) in [
+            (&self.yes_token, &self.global_yes_ata),
+            (&self.no_token, &self.global_no_ata),
+        ] {
+            if ata.amount > 0 {
+                token_interface::burn(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token_interface::Burn {
+                            mint: mint.to_account_info(),
+                            from: ata.to_account_info(),
+                            authority: self.global_vault.to_account_info(),
+                        },
+                        global_vault_seeds,
+                    ),
+                    ata.amount,
+                )?;
+            }
+            token_interface::close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account: ata.to_account_info(),
+                    destination: self.payer.clone(),
+                    authority: self.global_vault.to_account_info(),
+                },
+                global_vault_seeds,
+            ))?;
+        }
+
+        emit!(MarketClosedEvent {
+            market: market_key,
+            payer: self.payer.key(),
+            unredeemed_amount,
+            dust_lamports,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    //  creator fees of spl collateral markets collect in the creator vault ata, see
+    //  creator_claim_collateral
+    fn sweep_creator_collateral(&self, creator_vault_seeds: &[&[&[u8]]]) -> Result<()> {
+        let (Some(mint), Some(vault_ata), Some(creator_ata), Some(token_program)) = (
+            self.collateral_mint.as_ref(),
+            self.creator_vault_collateral_ata.as_ref(),
+            self.creator_collateral_ata.as_ref(),
+            self.collateral_token_program.as_ref(),
+        ) else {
+            return err!(TakesFunError::MissingCollateralAccount);
+        };
+
+        if vault_ata.amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: vault_ata.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: creator_ata.to_account_info(),
+                        authority: self.creator_vault.to_account_info(),
+                    },
+                    creator_vault_seeds,
+                ),
+                vault_ata.amount,
+                mint.decimals,
+            )?;
+        }
+        token_interface::close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: vault_ata.to_account_info(),
+                destination: self.payer.clone(),
+                authority: self.creator_vault.to_account_info(),
+            },
+            creator_vault_seeds,
+        ))
+    }
+
+    fn collateral_token(&self) -> Option<CollateralToken<'info>> {
+        let mint = self.collateral_mint.as_ref()?;
+        Some(CollateralToken {
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+            token_program: self.collateral_token_program.as_ref()?.to_account_info(),
+            vault_ata: self.global_collateral_ata.as_ref()?.to_account_info(),
+            user_ata: self.team_wallet_collateral_ata.as_ref()?.to_account_info(),
+        })
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    constants::{CATEGORICAL_MARKET, CONFIG, CREATOR, MARKET_VAULT},
+    errors::*,
+    state::{categorical_market::*, config::*},
+    utils::*,
+};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+#[instruction(market_info: String)]
+pub struct CreateCategoricalMarket<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CategoricalMarket::INIT_SPACE,
+        seeds = [CATEGORICAL_MARKET.as_bytes(), &market_info.to_hashed_bytes()],
+        bump
+    )]
+    market: Box<Account<'info, CategoricalMarket>>,
+
+    /// CHECK: CREATOR vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub creator_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(address = system_program::ID)]
+    system_program: Program<'info, System>,
+}
+
+impl<'info> CreateCategoricalMarket<'info> {
+    pub fn handler(
+        &mut self,
+        market_info: String,
+        outcome_count: u8,
+        soulbound: bool,
+    ) -> Result<()> {
+        require!(
+            !self.global_config.is_paused(Config::PAUSE_CREATE),
+            TakesFunError::ProgramPaused
+        );
+        require!(
+            (2..=MAX_OUTCOMES).contains(&(outcome_count as usize)),
+            TakesFunError::InvalidOutcome
+        );
+        require!(
+            market_info.len() <= MAX_CATEGORICAL_INFO_LEN,
+            TakesFunError::ValueTooLarge
+        );
+        self.global_config.validate_market_info(&market_info)?;
+
+        let market = &mut self.market;
+        market.creator = self.creator.key();
+        market.outcome_count = outcome_count;
+        market.outcomes = Vec::with_capacity(outcome_count as usize);
+        market.is_completed = false;
+        market.frozen = false;
+        market.created_at = Clock::get()?.unix_timestamp;
+        market.market_info = market_info;
+        market.max_wallet_sol = self.global_config.max_wallet_sol;
+        market.max_wallet_share_bps = self.global_config.max_wallet_share_bps;
+        market.launch_phase = self.global_config.launch_phase;
+        market.completed_outcomes = 0;
+        market.has_market_vault = true;
+        market.soulbound = soulbound;
+
+        //  initialize creator vault if needed
+        if self.creator_vault.lamports() == 0 {
+            sol_transfer_from_user(
+                &self.creator,
+                self.creator_vault.clone(),
+                &self.system_program,
+                1000000,
+            )?;
+        }
+
+        //  the market vault holds the SOL reserves, keep it rent exempt
+        if self.market_vault.lamports() == 0 {
+            sol_transfer_from_user(
+                &self.creator,
+                self.market_vault.clone(),
+                &self.system_program,
+                Rent::get()?.minimum_balance(0),
+            )?;
+        }
+
+        Ok(())
+    }
+}
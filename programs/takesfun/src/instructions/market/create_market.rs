@@ -1,14 +1,14 @@
 use crate::{
-    constants::{CONFIG, CREATOR, GLOBAL, MARKET, METADATA, YES_NAME},
+    constants::{CONFIG, CREATOR, GLOBAL, MARKET, MARKET_VAULT, POSITION, WHITELIST, YES_NAME},
+    errors::*,
     events::LaunchEvent,
-    state::{config::*, market::*},
-    utils::sol_transfer_from_user,
+    state::{config::*, market::*, position::*, whitelist::*},
+    utils::*,
 };
 use anchor_lang::{prelude::*, solana_program::sysvar::SysvarId, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    metadata::{self, mpl_token_metadata::types::DataV2, Metadata},
-    token::{self, spl_token::instruction::AuthorityType, Mint, Token},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 #[derive(Accounts)]
@@ -28,10 +28,18 @@ pub struct CreateMarket<'info> {
     )]
     pub global_vault: AccountInfo<'info>,
 
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
     /// CHECK: CREATOR vault pda which stores SOL
     #[account(
         mut,
-        seeds = [CREATOR.as_bytes(), &creator.key().to_bytes(), &market.key().to_bytes()],
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
         bump,
     )]
     pub creator_vault: AccountInfo<'info>,
@@ -39,73 +47,165 @@ pub struct CreateMarket<'info> {
     #[account(mut)]
     creator: Signer<'info>,
 
-    #[account(
-        init,
-        payer = creator,
-        mint::decimals = global_config.token_decimals_config,
-        mint::authority = global_vault.key(),
-    )]
-    yes_token: Box<Account<'info, Mint>>,
+    //  created in instruction under token_program
+    #[account(mut)]
+    yes_token: Signer<'info>,
 
-    pub no_token: Box<Account<'info, Mint>>,
+    //  soulbound mints are minted by the initial buy
+    #[account(mut, mint::token_program = token_program)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         init,
         payer = creator,
-        space = 320,
+        space = 8 + Market::INIT_SPACE,
         seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
         bump
     )]
     market: Box<Account<'info, Market>>,
 
-    /// CHECK: passed to token metadata program
-    #[account(mut,
+    /// CHECK: created in instruction
+    #[account(
+        mut,
         seeds = [
-            METADATA.as_bytes(),
-            metadata::ID.as_ref(),
+            global_vault.key().as_ref(),
+            token_program.key().as_ref(),
             yes_token.key().as_ref(),
         ],
         bump,
-        seeds::program = metadata::ID
+        seeds::program = associated_token::ID
     )]
-    yes_token_metadata_account: UncheckedAccount<'info>,
+    global_yes_token_account: UncheckedAccount<'info>,
 
-    /// CHECK: passed to token metadata program
+    /// CHECK: created in mint_no_token
     #[account(
         mut,
         seeds = [
-            METADATA.as_bytes(),
-            metadata::ID.as_ref(),
+            global_vault.key().as_ref(),
+            token_program.key().as_ref(),
             no_token.key().as_ref(),
         ],
         bump,
-        seeds::program = metadata::ID
+        seeds::program = associated_token::ID
     )]
-    no_token_metadata_account: UncheckedAccount<'info>,
+    global_no_token_account: UncheckedAccount<'info>,
 
-    /// CHECK: created in instruction
+    /// CHECK: should be same with the address in the global_config, initial buy only
+    #[account(
+        mut,
+        constraint = global_config.team_wallet == team_wallet.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub team_wallet: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: should be same with the address in the global_config, initial buy only
+    #[account(
+        mut,
+        constraint = global_config.team_wallet2 == team_wallet2.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub team_wallet2: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: ata of the creator, created on the initial buy
     #[account(
         mut,
         seeds = [
-            global_vault.key().as_ref(),
-            token::spl_token::ID.as_ref(),
+            creator.key().as_ref(),
+            token_program.key().as_ref(),
             yes_token.key().as_ref(),
         ],
         bump,
         seeds::program = associated_token::ID
     )]
-    global_yes_token_account: UncheckedAccount<'info>,
+    creator_yes_ata: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: ata of the creator, created on the initial buy
+    #[account(
+        mut,
+        seeds = [
+            creator.key().as_ref(),
+            token_program.key().as_ref(),
+            no_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    creator_no_ata: Option<UncheckedAccount<'info>>,
+
+    //  position of the initial buy, see swap
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [POSITION.as_bytes(), &market.key().to_bytes(), &creator.key().to_bytes()],
+        bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    //  whitelist entry of the creator, gives the initial buy its fee tier and launch phase
+    //  access like a swap of the creator
+    #[account(
+        mut,
+        seeds = [WHITELIST.as_bytes(), &creator.key().to_bytes()],
+        bump
+    )]
+    pub whitelist: Option<Account<'info, Whitelist>>,
+
+    //  spl collateral accounts, only passed for markets whose collateral is not native sol
+    pub collateral_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = global_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub global_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = team_wallet,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub team_wallet_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = team_wallet2,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub team_wallet2_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = creator_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_vault_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub collateral_token_program: Option<Interface<'info, TokenInterface>>,
 
     #[account(address = system_program::ID)]
     system_program: Program<'info, System>,
     #[account(address = Rent::id())]
     rent: Sysvar<'info, Rent>,
-    #[account(address = token::ID)]
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     associated_token_program: Program<'info, AssociatedToken>,
-    #[account(address = metadata::ID)]
-    mpl_token_metadata_program: Program<'info, Metadata>,
     // //  team wallet
     // /// CHECK: should be same with the address in the global_config
     // #[account(
@@ -116,19 +216,30 @@ pub struct CreateMarket<'info> {
 }
 
 impl<'info> CreateMarket<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn handler(
         &mut self,
 
         // metadata
         yes_symbol: String,
         yes_uri: String,
+        soulbound: bool,
 
         market_info: String,
 
+        scalar_bounds: Option<ScalarBounds>,
+        initial_buy: Option<InitialBuy>,
+
         global_vault_bump: u8,
+        market_vault_bump: u8,
     ) -> Result<()> {
         msg!("CreateMarket start");
+        self.global_config.validate_market_info(&market_info)?;
         let global_config = &self.global_config;
+        require!(
+            !global_config.is_paused(Config::PAUSE_CREATE),
+            TakesFunError::ProgramPaused
+        );
         let creator = &self.creator;
         let yes_token = &self.yes_token;
         let no_token = &self.no_token;
@@ -141,6 +252,7 @@ impl<'info> CreateMarket<'info> {
         market.yes_token_mint = yes_token.key();
         market.no_token_mint = no_token.key();
         market.creator = creator.key();
+        market.payer = creator.key();
 
         //yes
         market.virtual_yes_sol_reserves = global_config.initial_virtual_yes_sol_reserves_config;
@@ -156,77 +268,52 @@ impl<'info> CreateMarket<'info> {
 
         //market info
         market.market_info = market_info;
+        market.version = Market::VERSION;
 
-        // create global yes token account
-        associated_token::create(CpiContext::new(
-            self.associated_token_program.to_account_info(),
-            associated_token::Create {
-                payer: creator.to_account_info(),
-                associated_token: global_yes_token_account.to_account_info(),
-                authority: global_vault.to_account_info(),
+        market.max_wallet_sol = global_config.max_wallet_sol;
+        market.max_wallet_share_bps = global_config.max_wallet_share_bps;
+
+        market.created_at = Clock::get()?.unix_timestamp;
+        market.launch_phase = global_config.launch_phase;
+
+        market.set_bounds(scalar_bounds)?;
+        market.has_market_vault = true;
+
+        //  the global vault ata holds the reserves of spl collateral markets
+        if let Some(collateral_mint) = self.collateral_mint.as_ref() {
+            require!(
+                self.global_collateral_ata.is_some(),
+                TakesFunError::MissingCollateralAccount
+            );
+            market.collateral_mint = collateral_mint.key();
+        }
+
+        //  both sides of a market are soulbound or neither is
+        require!(
+            is_soulbound(&self.no_token.to_account_info())? == soulbound,
+            TakesFunError::SoulboundMismatch
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        //  create the yes mint and its metadata, mint yes tokens to market
+        create_outcome_mint(
+            CreateOutcomeMint {
                 mint: yes_token.to_account_info(),
+                payer: creator.to_account_info(),
+                global_vault: global_vault.to_account_info(),
+                global_token_account: global_yes_token_account.to_account_info(),
                 token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
                 system_program: self.system_program.to_account_info(),
             },
-        ))?;
-
-        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
-
-        // mint yes tokens to market
-        token::mint_to(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::MintTo {
-                    mint: yes_token.to_account_info(),
-                    to: global_yes_token_account.to_account_info(),
-                    authority: global_vault.to_account_info(),
-                },
-                signer_seeds,
-            ),
+            signer_seeds,
+            global_config.token_decimals_config,
             global_config.token_supply_config,
-        )?;
-
-        // create yes token metadata
-        metadata::create_metadata_accounts_v3(
-            CpiContext::new_with_signer(
-                self.mpl_token_metadata_program.to_account_info(),
-                metadata::CreateMetadataAccountsV3 {
-                    metadata: self.yes_token_metadata_account.to_account_info(),
-                    mint: yes_token.to_account_info(),
-                    mint_authority: global_vault.to_account_info(),
-                    payer: creator.to_account_info(),
-                    update_authority: global_vault.to_account_info(),
-                    system_program: self.system_program.to_account_info(),
-                    rent: self.rent.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            DataV2 {
-                name: yes_name.to_string(),
-                symbol: yes_symbol,
-                uri: yes_uri,
-                seller_fee_basis_points: 0,
-                creators: None,
-                collection: None,
-                uses: None,
-            },
-            false,
-            true,
-            None,
-        )?;
-
-        //  revoke mint authority
-        token::set_authority(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::SetAuthority {
-                    current_authority: global_vault.to_account_info(),
-                    account_or_mint: yes_token.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            AuthorityType::MintTokens,
-            None,
+            soulbound,
+            yes_name.to_string(),
+            yes_symbol,
+            yes_uri,
         )?;
 
         market.is_completed = false;
@@ -238,14 +325,14 @@ impl<'info> CreateMarket<'info> {
             market: self.market.key(),
 
             yes_mint: self.yes_token.key(),
-            yes_metadata: self.yes_token_metadata_account.key(),
+            yes_metadata: self.yes_token.key(),
             yes_real_reserve_lamport: self.market.real_yes_sol_reserves,
             yes_real_reserve_token: self.market.real_yes_token_reserves,
             yes_virtual_reserve_lamport: self.market.virtual_yes_sol_reserves,
             yes_virtual_reserve_token: self.market.virtual_yes_token_reserves,
 
             no_mint: self.no_token.key(),
-            no_metadata: self.no_token_metadata_account.key(),
+            no_metadata: self.no_token.key(),
             no_real_reserve_lamport: self.market.real_no_sol_reserves,
             no_real_reserve_token: self.market.real_no_token_reserves,
             no_virtual_reserve_lamport: self.market.virtual_no_sol_reserves,
@@ -267,6 +354,92 @@ impl<'info> CreateMarket<'info> {
             )?;
         }
 
+        //  the market vault holds the SOL reserves, keep it rent exempt
+        if self.market_vault.lamports() == 0 {
+            sol_transfer_from_user(
+                &self.creator,
+                self.market_vault.clone(),
+                &self.system_program,
+                Rent::get()?.minimum_balance(0),
+            )?;
+        }
+
+        if let Some(initial_buy) = initial_buy {
+            self.initial_buy(initial_buy, global_vault_bump, market_vault_bump)?;
+        }
+
         Ok(())
     }
+
+    fn initial_buy(
+        &mut self,
+        initial_buy: InitialBuy,
+        global_vault_bump: u8,
+        market_vault_bump: u8,
+    ) -> Result<()> {
+        let collateral_token = self.collateral_token();
+        execute_initial_buy(
+            InitialBuyAccounts {
+                global_config: &self.global_config,
+                market: &mut self.market,
+                payer: &self.creator,
+                buyer: self.creator.to_account_info(),
+                yes_token: self.yes_token.to_account_info(),
+                no_token: self.no_token.to_account_info(),
+                global_yes_ata: self.global_yes_token_account.to_account_info(),
+                global_no_ata: self.global_no_token_account.to_account_info(),
+                buyer_yes_ata: self
+                    .creator_yes_ata
+                    .as_ref()
+                    .map(|ata| ata.to_account_info()),
+                buyer_no_ata: self
+                    .creator_no_ata
+                    .as_ref()
+                    .map(|ata| ata.to_account_info()),
+                global_vault: self.global_vault.to_account_info(),
+                market_vault: self.market_vault.to_account_info(),
+                creator_vault: self.creator_vault.to_account_info(),
+                team_wallet: self
+                    .team_wallet
+                    .as_ref()
+                    .map(|wallet| wallet.to_account_info()),
+                team_wallet2: self
+                    .team_wallet2
+                    .as_ref()
+                    .map(|wallet| wallet.to_account_info()),
+                team_wallet_collateral_ata: self
+                    .team_wallet_collateral_ata
+                    .as_ref()
+                    .map(|ata| ata.to_account_info()),
+                team_wallet2_collateral_ata: self
+                    .team_wallet2_collateral_ata
+                    .as_ref()
+                    .map(|ata| ata.to_account_info()),
+                creator_vault_collateral_ata: self
+                    .creator_vault_collateral_ata
+                    .as_ref()
+                    .map(|ata| ata.to_account_info()),
+                collateral_token,
+                position: self.position.as_mut(),
+                whitelist: self.whitelist.as_deref_mut(),
+                token_program: &self.token_program,
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: &self.system_program,
+            },
+            initial_buy,
+            global_vault_bump,
+            market_vault_bump,
+        )
+    }
+
+    fn collateral_token(&self) -> Option<CollateralToken<'info>> {
+        let mint = self.collateral_mint.as_ref()?;
+        Some(CollateralToken {
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+            token_program: self.collateral_token_program.as_ref()?.to_account_info(),
+            vault_ata: self.global_collateral_ata.as_ref()?.to_account_info(),
+            user_ata: self.creator_collateral_ata.as_ref()?.to_account_info(),
+        })
+    }
 }
@@ -1,18 +1,24 @@
 use crate::{
-    constants::{ CREATOR,  MARKET},
+    constants::{ CONFIG, CREATOR,  MARKET},
     errors::*,
-    state::{market::*},
+    state::{config::*, market::*},
 };
 
 use crate::utils::*;
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
-    token::{ Mint},
+    token_interface::{ Mint},
 };
 
 #[derive(Accounts)]
 pub struct CreeatorClaim<'info> {
 
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
     #[account(
         mut,
         constraint = market.creator == creator.key() @TakesFunError::IncorrectAuthority
@@ -29,14 +35,14 @@ pub struct CreeatorClaim<'info> {
     /// CHECK: CREATOR vault pda which stores SOL
     #[account(
         mut,
-        seeds = [CREATOR.as_bytes(), &creator.key().to_bytes(), &market.key().to_bytes()],
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
         bump,
     )]
     pub creator_vault: AccountInfo<'info>,
 
-    pub yes_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
 
-    pub no_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
@@ -45,10 +51,14 @@ pub struct CreeatorClaim<'info> {
 
 impl<'info> CreeatorClaim<'info> { 
     pub fn handler(&mut self, creator_vault_bump:u8) -> Result<()> {
+    require!(
+        !self.global_config.is_paused(Config::PAUSE_CLAIM),
+        TakesFunError::ProgramPaused
+    );
+    require!(!self.market.frozen, TakesFunError::MarketFrozen);
 
     let signer_seeds: &[&[&[u8]]] = &[&[
         CREATOR.as_bytes(),
-        &self.creator.key().to_bytes(),
         &self.market.key().to_bytes(),
         &[creator_vault_bump],
     ]];
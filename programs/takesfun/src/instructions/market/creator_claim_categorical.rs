@@ -0,0 +1,63 @@
+use crate::{
+    constants::{CONFIG, CREATOR},
+    errors::*,
+    state::{categorical_market::*, config::*},
+    utils::*,
+};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct CreatorClaimCategorical<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        constraint = market.creator == creator.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub creator: Signer<'info>,
+
+    market: Box<Account<'info, CategoricalMarket>>,
+
+    /// CHECK: CREATOR vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub creator_vault: AccountInfo<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatorClaimCategorical<'info> {
+    pub fn handler(&mut self, creator_vault_bump: u8) -> Result<()> {
+        require!(
+            !self.global_config.is_paused(Config::PAUSE_CLAIM),
+            TakesFunError::ProgramPaused
+        );
+        require!(!self.market.frozen, TakesFunError::MarketFrozen);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CREATOR.as_bytes(),
+            &self.market.key().to_bytes(),
+            &[creator_vault_bump],
+        ]];
+
+        //  the vault keeps its initial rent deposit
+        sol_transfer_with_signer(
+            self.creator_vault.clone(),
+            self.creator.to_account_info(),
+            &self.system_program,
+            signer_seeds,
+            self.creator_vault.lamports().saturating_sub(1000000),
+        )?;
+        msg!("SOL to user transfer complete");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    constants::{CONFIG, CREATOR},
+    errors::*,
+    state::{config::*, market::*},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct CreatorClaimCollateral<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        constraint = market.creator == creator.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = market.collateral_mint == collateral_mint.key() @TakesFunError::IncorrectCollateralMint
+    )]
+    market: Box<Account<'info, Market>>,
+
+    /// CHECK: CREATOR vault pda which stores SOL
+    #[account(
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub creator_vault: AccountInfo<'info>,
+
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = creator_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_vault_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl CreatorClaimCollateral<'_> {
+    pub fn handler(&mut self, creator_vault_bump: u8) -> Result<()> {
+        require!(
+            !self.global_config.is_paused(Config::PAUSE_CLAIM),
+            TakesFunError::ProgramPaused
+        );
+        require!(!self.market.frozen, TakesFunError::MarketFrozen);
+
+        let amount = self.creator_vault_collateral_ata.amount;
+        require!(amount > 0, TakesFunError::InvalidAmount);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CREATOR.as_bytes(),
+            &self.market.key().to_bytes(),
+            &[creator_vault_bump],
+        ]];
+
+        //  creator fees of spl collateral markets collect in the vault ata
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.collateral_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.creator_vault_collateral_ata.to_account_info(),
+                    mint: self.collateral_mint.to_account_info(),
+                    to: self.creator_collateral_ata.to_account_info(),
+                    authority: self.creator_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.collateral_mint.decimals,
+        )?;
+        msg!("Collateral to creator transfer complete: {}", amount);
+
+        Ok(())
+    }
+}
@@ -1,19 +1,25 @@
 use crate::{
-    constants::{ CREATOR,  MARKET},
+    constants::{ CONFIG, CREATOR,  MARKET},
     errors::*,
-    state::{market::*},
+    state::{config::*, market::*},
 };
 
 use crate::utils::*;
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
-    token::{ Mint},
+    token_interface::{ Mint},
 };
 
 #[derive(Accounts)]
 #[instruction(market_info: String)]
 pub struct CreeatorClaimSecond<'info> {
 
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
     #[account(
         mut,
         constraint = market.creator == creator.key() @TakesFunError::IncorrectAuthority
@@ -35,9 +41,9 @@ pub struct CreeatorClaimSecond<'info> {
     )]
     pub creator_vault: AccountInfo<'info>,
 
-    pub yes_token: Box<Account<'info, Mint>>,
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
 
-    pub no_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
@@ -45,7 +51,12 @@ pub struct CreeatorClaimSecond<'info> {
 }
 
 impl<'info> CreeatorClaimSecond<'info> { 
-    pub fn handler(&mut self, market_info: String, creator_vault_bump:u8) -> Result<()> {
+    pub fn handler(&mut self, _market_info: String, creator_vault_bump:u8) -> Result<()> {
+    require!(
+        !self.global_config.is_paused(Config::PAUSE_CLAIM),
+        TakesFunError::ProgramPaused
+    );
+    require!(!self.market.frozen, TakesFunError::MarketFrozen);
 
     let signer_seeds: &[&[&[u8]]] = &[&[
         CREATOR.as_bytes(),
@@ -0,0 +1,114 @@
+use crate::{
+    constants::{CONFIG, GLOBAL, MARKET_VAULT},
+    errors::*,
+    events::ResolvedEvent,
+    state::{config::*, market::*},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        constraint = market.yes_token_mint == yes_token.key() @TakesFunError::IncorrectYesToken,
+        constraint = market.no_token_mint == no_token.key() @TakesFunError::IncorrectNoToken
+    )]
+    market: Box<Account<'info, Market>>,
+
+    /// CHECK: global vault pda which owns the outcome and collateral atas
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &global_vault.key(),
+            &yes_token.key(),
+            yes_token.to_account_info().owner
+        )
+    )]
+    global_yes_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &global_vault.key(),
+            &no_token.key(),
+            no_token.to_account_info().owner
+        )
+    )]
+    global_no_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    //  only passed for markets whose collateral is not native sol
+    #[account(
+        constraint = global_collateral_ata.mint == market.collateral_mint @TakesFunError::IncorrectCollateralMint,
+        constraint = global_collateral_ata.owner == global_vault.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub global_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+impl FinalizeResolution<'_> {
+    pub fn handler(&mut self) -> Result<()> {
+        require!(
+            self.market.has_market_vault,
+            TakesFunError::MarketVaultNotMigrated
+        );
+        //  the payout pools can not promise more than the vault holds, spl collateral of all
+        //  markets shares the global vault ata
+        let available = if self.market.is_sol_collateral() {
+            self.market_vault
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0))
+        } else {
+            self.global_collateral_ata
+                .as_ref()
+                .ok_or(TakesFunError::MissingCollateralAccount)?
+                .amount
+        };
+
+        let market = &mut self.market;
+
+        //  everything outside the global vault is held by traders
+        let yes_outstanding = self.yes_token.supply - self.global_yes_ata.amount;
+        let no_outstanding = self.no_token.supply - self.global_no_ata.amount;
+        market.finalize_resolution(
+            Clock::get()?.unix_timestamp,
+            yes_outstanding,
+            no_outstanding,
+            available,
+        )?;
+
+        emit!(ResolvedEvent {
+            authority: market.proposer,
+            market: market.key(),
+            resolved_value: market.resolved_value,
+            yes_payout_pool: market.yes_payout_pool,
+            no_payout_pool: market.no_payout_pool,
+            yes_outstanding,
+            no_outstanding,
+        });
+        Ok(())
+    }
+}
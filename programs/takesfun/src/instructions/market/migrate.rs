@@ -0,0 +1,264 @@
+use crate::{
+    constants::{AMM_POOL, CONFIG, GLOBAL, MARKET_VAULT},
+    errors::*,
+    events::MigrationEvent,
+    state::{config::*, market::*},
+    utils::*,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        constraint = migration_authority.key() == global_config.migration_authority @TakesFunError::InvalidMigrationAuthority
+    )]
+    pub migration_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = market.yes_token_mint == yes_token.key() @TakesFunError::IncorrectYesToken,
+        constraint = market.no_token_mint == no_token.key() @TakesFunError::IncorrectNoToken
+    )]
+    market: Box<Account<'info, Market>>,
+
+    /// CHECK: global vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: yes ata of global vault
+    #[account(
+        mut,
+        seeds = [
+            global_vault.key().as_ref(),
+            token_program.key().as_ref(),
+            yes_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    global_yes_ata: AccountInfo<'info>,
+
+    /// CHECK: no ata of global vault
+    #[account(
+        mut,
+        seeds = [
+            global_vault.key().as_ref(),
+            token_program.key().as_ref(),
+            no_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    global_no_ata: AccountInfo<'info>,
+
+    /// CHECK: created by the amm program
+    #[account(
+        mut,
+        seeds = [AMM_POOL.as_bytes(), yes_token.key().as_ref()],
+        bump,
+        seeds::program = amm_program.key()
+    )]
+    yes_pool: AccountInfo<'info>,
+
+    /// CHECK: ata of the pool, created by the amm program
+    #[account(
+        mut,
+        seeds = [
+            yes_pool.key().as_ref(),
+            token_program.key().as_ref(),
+            yes_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    yes_pool_token_account: AccountInfo<'info>,
+
+    /// CHECK: created by the amm program
+    #[account(
+        mut,
+        seeds = [AMM_POOL.as_bytes(), no_token.key().as_ref()],
+        bump,
+        seeds::program = amm_program.key()
+    )]
+    no_pool: AccountInfo<'info>,
+
+    /// CHECK: ata of the pool, created by the amm program
+    #[account(
+        mut,
+        seeds = [
+            no_pool.key().as_ref(),
+            token_program.key().as_ref(),
+            no_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    no_pool_token_account: AccountInfo<'info>,
+
+    /// CHECK: should be same with the address in the global_config
+    #[account(
+        executable,
+        constraint = amm_program.key() == global_config.amm_program @TakesFunError::InvalidMeteoraProgram
+    )]
+    amm_program: AccountInfo<'info>,
+
+    #[account(address = system_program::ID)]
+    system_program: Program<'info, System>,
+    token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> Migrate<'info> {
+    pub fn handler(&mut self, global_vault_bump: u8, market_vault_bump: u8) -> Result<()> {
+        let market = &self.market;
+        require!(!market.is_completed, TakesFunError::MarketAlreadyCompleted);
+        //  the market vault pays the winners of a resolved or resolving market
+        require!(!market.resolved, TakesFunError::MarketResolved);
+        require!(
+            !market.has_resolution_proposal(),
+            TakesFunError::ResolutionAlreadyProposed
+        );
+        //  the amm pools are seeded with native sol
+        require!(
+            market.is_sol_collateral(),
+            TakesFunError::UnsupportedCollateral
+        );
+        require!(
+            market.yes_completed || market.no_completed,
+            TakesFunError::CurveNotCompleted
+        );
+        //  the amm can not hold soulbound tokens, soulbound markets wait for their resolution
+        require!(
+            !is_soulbound(&self.yes_token.to_account_info())?
+                && !is_soulbound(&self.no_token.to_account_info())?,
+            TakesFunError::SoulboundMigration
+        );
+        require!(
+            self.yes_pool.data_is_empty() && self.no_pool.data_is_empty(),
+            TakesFunError::AmmAlreadyExists
+        );
+
+        //  both sides move to the amm with whatever is left on their curve
+        let yes_token_amount = market.real_yes_token_reserves;
+        let yes_sol_amount = market.real_yes_sol_reserves;
+        let no_token_amount = market.real_no_token_reserves;
+        let no_sol_amount = market.real_no_sol_reserves;
+
+        //  the global vault provides the liquidity, fund it from the market vault
+        if market.has_market_vault {
+            let market_key = market.key();
+            let market_vault_seeds: &[&[&[u8]]] = &[&[
+                MARKET_VAULT.as_bytes(),
+                market_key.as_ref(),
+                &[market_vault_bump],
+            ]];
+            sol_transfer_with_signer(
+                self.market_vault.clone(),
+                self.global_vault.clone(),
+                &self.system_program,
+                market_vault_seeds,
+                yes_sol_amount
+                    .checked_add(no_sol_amount)
+                    .ok_or(TakesFunError::ArithmeticError)?,
+            )?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        amm_initialize_pool(
+            self.amm_program.clone(),
+            AmmInitializePool {
+                pool: self.yes_pool.clone(),
+                mint: self.yes_token.to_account_info(),
+                pool_token_account: self.yes_pool_token_account.clone(),
+                payer: self.migration_authority.to_account_info(),
+                liquidity_provider: self.global_vault.clone(),
+                provider_token_account: self.global_yes_ata.clone(),
+                token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            signer_seeds,
+            yes_token_amount,
+            yes_sol_amount,
+        )?;
+        msg!("yes pool created");
+
+        amm_initialize_pool(
+            self.amm_program.clone(),
+            AmmInitializePool {
+                pool: self.no_pool.clone(),
+                mint: self.no_token.to_account_info(),
+                pool_token_account: self.no_pool_token_account.clone(),
+                payer: self.migration_authority.to_account_info(),
+                liquidity_provider: self.global_vault.clone(),
+                provider_token_account: self.global_no_ata.clone(),
+                token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            signer_seeds,
+            no_token_amount,
+            no_sol_amount,
+        )?;
+        msg!("no pool created");
+
+        let market = &mut self.market;
+        market.real_yes_token_reserves = 0;
+        market.real_yes_sol_reserves = 0;
+        market.real_no_token_reserves = 0;
+        market.real_no_sol_reserves = 0;
+        market.is_completed = true;
+        //  the amm owns the liquidity now, the market owes nothing
+        self.global_config
+            .reconcile_sol_liabilities(&mut market.reported_sol_liabilities, 0)?;
+
+        emit!(MigrationEvent {
+            market: market.key(),
+            amm_program: self.amm_program.key(),
+
+            yes_pool: self.yes_pool.key(),
+            yes_token_amount,
+            yes_sol_amount,
+
+            no_pool: self.no_pool.key(),
+            no_token_amount,
+            no_sol_amount,
+
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
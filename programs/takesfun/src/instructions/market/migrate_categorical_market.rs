@@ -0,0 +1,42 @@
+use crate::{errors::*, state::categorical_market::*, utils::*};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct MigrateCategoricalMarket<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: categorical market with an older layout, deserialized after the realloc
+    #[account(mut, owner = crate::ID)]
+    pub market: AccountInfo<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateCategoricalMarket<'_> {
+    pub fn handler(&mut self) -> Result<()> {
+        //  the fields appended after market_info deserialize from the zeroed tail, the market
+        //  keeps no limits and its reserves stay in the global vault until migrate_categorical_market_vault
+        let new_len = 8 + CategoricalMarket::INIT_SPACE;
+        require!(
+            self.market.data_len() < new_len,
+            TakesFunError::MarketAlreadyUpgraded
+        );
+        let rent = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(self.market.lamports());
+        if rent > 0 {
+            sol_transfer_from_user(&self.payer, self.market.clone(), &self.system_program, rent)?;
+        }
+        self.market.realloc(new_len, true)?;
+
+        let market = CategoricalMarket::try_deserialize(&mut &self.market.try_borrow_data()?[..])?;
+        msg!(
+            "Categorical market migration complete: {} outcomes",
+            market.outcomes.len()
+        );
+
+        Ok(())
+    }
+}
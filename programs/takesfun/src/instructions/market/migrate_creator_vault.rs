@@ -0,0 +1,67 @@
+use crate::{
+    constants::{CREATOR, MARKET},
+    errors::*,
+    state::market::*,
+    utils::*,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+#[instruction(old_creator: Pubkey)]
+pub struct MigrateCreatorVault<'info> {
+    #[account(
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Account<'info, Market>,
+
+    /// CHECK: legacy creator vault pda seeded by the creator key
+    #[account(
+        mut,
+        seeds = [CREATOR.as_bytes(), &old_creator.to_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub old_creator_vault: AccountInfo<'info>,
+
+    /// CHECK: CREATOR vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub creator_vault: AccountInfo<'info>,
+
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateCreatorVault<'info> {
+    pub fn handler(&mut self, old_creator: Pubkey, old_creator_vault_bump: u8) -> Result<()> {
+        let amount = self.old_creator_vault.lamports();
+        require!(amount > 0, TakesFunError::InvalidAmount);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CREATOR.as_bytes(),
+            &old_creator.to_bytes(),
+            &self.market.key().to_bytes(),
+            &[old_creator_vault_bump],
+        ]];
+
+        //  move the whole legacy balance, the emptied system account is purged by the runtime
+        sol_transfer_with_signer(
+            self.old_creator_vault.clone(),
+            self.creator_vault.clone(),
+            &self.system_program,
+            signer_seeds,
+            amount,
+        )?;
+        msg!("Creator vault migration complete: {} lamports", amount);
+
+        Ok(())
+    }
+}
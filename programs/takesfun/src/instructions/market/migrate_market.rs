@@ -0,0 +1,43 @@
+use crate::{state::market::*, utils::*};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: market with an older layout, deserialized after the realloc
+    #[account(mut, owner = crate::ID)]
+    pub market: AccountInfo<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateMarket<'_> {
+    pub fn handler(&mut self) -> Result<()> {
+        //  older layouts are shorter, the new fields deserialize from the zeroed tail
+        let new_len = 8 + Market::INIT_SPACE;
+        if self.market.data_len() < new_len {
+            let rent = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(self.market.lamports());
+            if rent > 0 {
+                sol_transfer_from_user(
+                    &self.payer,
+                    self.market.clone(),
+                    &self.system_program,
+                    rent,
+                )?;
+            }
+            self.market.realloc(new_len, true)?;
+        }
+
+        let mut market = Market::try_deserialize(&mut &self.market.try_borrow_data()?[..])?;
+        market.upgrade()?;
+        market.try_serialize(&mut &mut self.market.try_borrow_mut_data()?[..])?;
+        msg!("Market migration complete: version {}", market.version);
+
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
 use crate::{
-    constants::{CONFIG, GLOBAL, METADATA, NO_NAME},
+    constants::{CONFIG, GLOBAL, NO_NAME},
+    errors::*,
     state::config::*,
+    utils::*,
 };
 use anchor_lang::{prelude::*, solana_program::sysvar::SysvarId, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    metadata::{self, mpl_token_metadata::types::DataV2, Metadata},
-    token::{self, spl_token::instruction::AuthorityType, Mint, Token},
+    token_interface::TokenInterface,
 };
 
 #[derive(Accounts)]
@@ -30,33 +31,16 @@ pub struct MintNoToken<'info> {
     #[account(mut)]
     creator: Signer<'info>,
 
-    #[account(
-        init,
-        payer = creator,
-        mint::decimals = global_config.token_decimals_config,
-        mint::authority = global_vault.key(),
-    )]
-    no_token: Box<Account<'info, Mint>>,
-
-    /// CHECK: passed to token metadata program
-    #[account(
-        mut,
-        seeds = [
-            METADATA.as_bytes(),
-            metadata::ID.as_ref(),
-            no_token.key().as_ref(),
-        ],
-        bump,
-        seeds::program = metadata::ID
-    )]
-    no_token_metadata_account: UncheckedAccount<'info>,
+    //  created in instruction under token_program
+    #[account(mut)]
+    no_token: Signer<'info>,
 
     /// CHECK: created in instruction
     #[account(
         mut,
         seeds = [
             global_vault.key().as_ref(),
-            token::spl_token::ID.as_ref(),
+            token_program.key().as_ref(),
             no_token.key().as_ref(),
         ],
         bump,
@@ -68,12 +52,9 @@ pub struct MintNoToken<'info> {
     system_program: Program<'info, System>,
     #[account(address = Rent::id())]
     rent: Sysvar<'info, Rent>,
-    #[account(address = token::ID)]
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
     #[account(address = associated_token::ID)]
     associated_token_program: Program<'info, AssociatedToken>,
-    #[account(address = metadata::ID)]
-    mpl_token_metadata_program: Program<'info, Metadata>,
 }
 
 impl<'info> MintNoToken<'info> {
@@ -83,85 +64,37 @@ impl<'info> MintNoToken<'info> {
         // metadata
         no_symbol: String,
         no_uri: String,
+        //  non transferable no token, the yes token of the market has to match
+        soulbound: bool,
         global_vault_bump: u8,
     ) -> Result<()> {
         let global_config = &self.global_config;
-        let creator = &self.creator;
-        let no_token: &Box<Account<'info, Mint>> = &self.no_token;
-        let global_no_token_account = &self.global_no_token_account;
-        let global_vault = &self.global_vault;
+        require!(
+            !global_config.is_paused(Config::PAUSE_CREATE),
+            TakesFunError::ProgramPaused
+        );
         let no_name = NO_NAME;
 
-        // create global token account
-        associated_token::create(CpiContext::new(
-            self.associated_token_program.to_account_info(),
-            associated_token::Create {
-                payer: creator.to_account_info(),
-                associated_token: global_no_token_account.to_account_info(),
-                authority: global_vault.to_account_info(),
-                mint: no_token.to_account_info(),
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        //  create the mint and its metadata, mint tokens to bonding curve
+        create_outcome_mint(
+            CreateOutcomeMint {
+                mint: self.no_token.to_account_info(),
+                payer: self.creator.to_account_info(),
+                global_vault: self.global_vault.to_account_info(),
+                global_token_account: self.global_no_token_account.to_account_info(),
                 token_program: self.token_program.to_account_info(),
+                associated_token_program: self.associated_token_program.to_account_info(),
                 system_program: self.system_program.to_account_info(),
             },
-        ))?;
-
-        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
-
-        // mint tokens to bonding curve & team
-        token::mint_to(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::MintTo {
-                    mint: no_token.to_account_info(),
-                    to: global_no_token_account.to_account_info(),
-                    authority: global_vault.to_account_info(),
-                },
-                signer_seeds,
-            ),
+            signer_seeds,
+            global_config.token_decimals_config,
             global_config.token_supply_config,
-        )?;
-
-        // create metadata
-        metadata::create_metadata_accounts_v3(
-            CpiContext::new_with_signer(
-                self.mpl_token_metadata_program.to_account_info(),
-                metadata::CreateMetadataAccountsV3 {
-                    metadata: self.no_token_metadata_account.to_account_info(),
-                    mint: no_token.to_account_info(),
-                    mint_authority: global_vault.to_account_info(),
-                    payer: creator.to_account_info(),
-                    update_authority: global_vault.to_account_info(),
-                    system_program: self.system_program.to_account_info(),
-                    rent: self.rent.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            DataV2 {
-                name: no_name.to_string(),
-                symbol: no_symbol,
-                uri: no_uri,
-                seller_fee_basis_points: 0,
-                creators: None,
-                collection: None,
-                uses: None,
-            },
-            false,
-            true,
-            None,
-        )?;
-
-        //  revoke mint authority
-        token::set_authority(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::SetAuthority {
-                    current_authority: global_vault.to_account_info(),
-                    account_or_mint: no_token.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            AuthorityType::MintTokens,
-            None,
+            soulbound,
+            no_name.to_string(),
+            no_symbol,
+            no_uri,
         )?;
 
         // emit!(LaunchEvent {
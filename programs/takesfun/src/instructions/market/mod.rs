@@ -13,3 +13,39 @@ pub mod creator_claim_second;
 pub use creator_claim_second::*;
 pub mod swap_second;
 pub use swap_second::*;
+pub mod migrate_creator_vault;
+pub use migrate_creator_vault::*;
+pub mod nominate_creator;
+pub use nominate_creator::*;
+pub mod accept_creator;
+pub use accept_creator::*;
+pub mod migrate;
+pub use migrate::*;
+pub mod create_categorical_market;
+pub use create_categorical_market::*;
+pub mod add_categorical_outcome;
+pub use add_categorical_outcome::*;
+pub mod swap_categorical;
+pub use swap_categorical::*;
+pub mod creator_claim_categorical;
+pub use creator_claim_categorical::*;
+pub mod resolve_market;
+pub use resolve_market::*;
+pub mod challenge_resolution;
+pub use challenge_resolution::*;
+pub mod finalize_resolution;
+pub use finalize_resolution::*;
+pub mod redeem;
+pub use redeem::*;
+pub mod creator_claim_collateral;
+pub use creator_claim_collateral::*;
+pub mod audit_market;
+pub use audit_market::*;
+pub mod close_market;
+pub use close_market::*;
+pub mod migrate_market;
+pub use migrate_market::*;
+pub mod update_market_metadata;
+pub use update_market_metadata::*;
+pub mod migrate_categorical_market;
+pub use migrate_categorical_market::*;
@@ -0,0 +1,28 @@
+use crate::{errors::TakesFunError, events::CreatorNominatedEvent, state::market::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct NominateCreator<'info> {
+    // Current creator
+    #[account(
+        constraint = market.creator == creator.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub creator: Signer<'info>,
+
+    //  Stores creator address
+    #[account(mut)]
+    market: Account<'info, Market>,
+}
+
+impl NominateCreator<'_> {
+    pub fn process(&mut self, new_creator: Pubkey) -> Result<()> {
+        self.market.pending_creator = new_creator;
+
+        emit!(CreatorNominatedEvent {
+            market: self.market.key(),
+            creator: self.creator.key(),
+            pending_creator: new_creator,
+        });
+        Ok(())
+    }
+}
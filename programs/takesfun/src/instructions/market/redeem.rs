@@ -0,0 +1,195 @@
+use crate::{
+    constants::{CONFIG, GLOBAL, MARKET_VAULT},
+    errors::*,
+    events::RedeemEvent,
+    state::{config::*, market::*},
+    utils::*,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+#[instruction(token_type: u8)]
+pub struct Redeem<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        constraint = market.yes_token_mint == yes_token.key() @TakesFunError::IncorrectYesToken,
+        constraint = market.no_token_mint == no_token.key() @TakesFunError::IncorrectNoToken
+    )]
+    market: Box<Account<'info, Market>>,
+
+    /// CHECK: global vault pda which owns the collateral atas
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: ata of user for the redeemed side
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &if token_type == 0 { no_token.key() } else { yes_token.key() },
+            &token_program.key()
+        )
+    )]
+    user_ata: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    //  spl collateral accounts, only passed for markets whose collateral is not native sol
+    pub collateral_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = global_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub global_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub collateral_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> Redeem<'info> {
+    pub fn handler(
+        &mut self,
+        token_type: u8,
+        amount: u64,
+        global_vault_bump: u8,
+        market_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            !self.global_config.is_paused(Config::PAUSE_CLAIM),
+            TakesFunError::ProgramPaused
+        );
+        require!(!self.market.frozen, TakesFunError::MarketFrozen);
+        require!(self.market.resolved, TakesFunError::MarketNotResolved);
+        require!(
+            self.market.has_market_vault,
+            TakesFunError::MarketVaultNotMigrated
+        );
+        require!(amount > 0, TakesFunError::InvalidAmount);
+
+        let sol_amount = self
+            .market
+            .redemption_amount(token_type, amount)
+            .ok_or(TakesFunError::ArithmeticError)?;
+
+        let mint = match token_type {
+            0 => self.no_token.to_account_info(),
+            _ => self.yes_token.to_account_info(),
+        };
+        token_interface::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint,
+                    from: self.user_ata.clone(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let collateral_token = self.collateral_token();
+        let market = &mut self.market;
+        let (redeemed, outstanding) = match token_type {
+            0 => (market.no_redeemed, market.no_outstanding),
+            _ => (market.yes_redeemed, market.yes_outstanding),
+        };
+        let redeemed = redeemed
+            .checked_add(amount)
+            .ok_or(TakesFunError::OverflowOrUnderflowOccurred)?;
+        require!(redeemed <= outstanding, TakesFunError::InsufficientTokens);
+        match token_type {
+            0 => market.no_redeemed = redeemed,
+            _ => market.yes_redeemed = redeemed,
+        }
+
+        if sol_amount > 0 {
+            let market_key = market.key();
+            let market_vault_seeds: &[&[&[u8]]] = &[&[
+                MARKET_VAULT.as_bytes(),
+                market_key.as_ref(),
+                &[market_vault_bump],
+            ]];
+            let global_vault_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+            let collateral = Collateral::new(
+                market.collateral_mint,
+                Vault {
+                    account: self.market_vault.to_account_info(),
+                    signer_seeds: market_vault_seeds,
+                },
+                Vault {
+                    account: self.global_vault.to_account_info(),
+                    signer_seeds: global_vault_seeds,
+                },
+                &self.system_program,
+                collateral_token,
+            )?;
+            collateral.transfer_to_user(&self.user, sol_amount)?;
+        }
+
+        emit!(RedeemEvent {
+            user: self.user.key(),
+            market: market.key(),
+            token_type,
+            token_amount: amount,
+            sol_amount,
+        });
+        Ok(())
+    }
+
+    fn collateral_token(&self) -> Option<CollateralToken<'info>> {
+        let mint = self.collateral_mint.as_ref()?;
+        Some(CollateralToken {
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+            token_program: self.collateral_token_program.as_ref()?.to_account_info(),
+            vault_ata: self.global_collateral_ata.as_ref()?.to_account_info(),
+            user_ata: self.user_collateral_ata.as_ref()?.to_account_info(),
+        })
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{
+    constants::CONFIG,
+    errors::*,
+    events::ResolutionProposedEvent,
+    state::{config::*, market::*},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    // Current admin or curator
+    #[account(
+        constraint = global_config.authority == *admin.key
+            || global_config.curator == *admin.key @TakesFunError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    market: Box<Account<'info, Market>>,
+}
+
+impl ResolveMarket<'_> {
+    pub fn process(&mut self, value: i64) -> Result<()> {
+        let market = &mut self.market;
+        market.propose_resolution(value, self.admin.key(), Clock::get()?.unix_timestamp)?;
+
+        emit!(ResolutionProposedEvent {
+            authority: self.admin.key(),
+            market: market.key(),
+            proposed_value: market.proposed_value,
+            challenge_deadline: market.challenge_deadline(),
+        });
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
 use anchor_lang::{system_program, prelude::*};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use crate::{
-    constants::{MARKET, CONFIG, GLOBAL, WHITELIST, CREATOR}, 
+    constants::{MARKET, MARKET_VAULT, CONFIG, GLOBAL, WHITELIST, CREATOR, POSITION}, 
     errors::*, 
-    state::{market::*,  config::*, whitelist::*}
+    state::{market::*,  config::*, whitelist::*, position::*},
+    utils::{Collateral, CollateralToken, Vault},
 };
 
 #[derive(Accounts)]
@@ -45,32 +46,42 @@ pub struct Swap<'info> {
     )]
     market: Account<'info, Market>,
 
-    /// CHECK: global vault pda which stores SOL
+    /// CHECK: global vault pda which owns the outcome and collateral atas
     #[account(
-        mut,
         seeds = [GLOBAL.as_bytes()],
         bump,
     )]
     pub global_vault: AccountInfo<'info>,
 
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
     /// CHECK: CREATOR vault pda which stores SOL
     #[account(
         mut,
-        seeds = [CREATOR.as_bytes(), &creator.key().to_bytes(), &market.key().to_bytes()],
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
         bump,
     )]
     pub creator_vault: AccountInfo<'info>,
 
-    pub yes_token: Box<Account<'info, Mint>>,
+    //  soulbound mints are minted and burned by the swap
+    #[account(mut, mint::token_program = token_program)]
+    pub yes_token: Box<InterfaceAccount<'info, Mint>>,
 
-    pub no_token: Box<Account<'info, Mint>>,
+    #[account(mut, mint::token_program = token_program)]
+    pub no_token: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: yes ata of global vault
     #[account(
         mut,
         seeds = [
             global_vault.key().as_ref(),
-            anchor_spl::token::spl_token::ID.as_ref(),
+            token_program.key().as_ref(),
             yes_token.key().as_ref(),
         ],
         bump,
@@ -83,7 +94,7 @@ pub struct Swap<'info> {
         mut,
         seeds = [
             global_vault.key().as_ref(),
-            anchor_spl::token::spl_token::ID.as_ref(),
+            token_program.key().as_ref(),
             no_token.key().as_ref(),
         ],
         bump,
@@ -96,7 +107,7 @@ pub struct Swap<'info> {
         mut,
         seeds = [
             user.key().as_ref(),
-            anchor_spl::token::spl_token::ID.as_ref(),
+            token_program.key().as_ref(),
             yes_token.key().as_ref(),
         ],
         bump,
@@ -109,7 +120,7 @@ pub struct Swap<'info> {
         mut,
         seeds = [
             user.key().as_ref(),
-            anchor_spl::token::spl_token::ID.as_ref(),
+            token_program.key().as_ref(),
             no_token.key().as_ref(),
         ],
         bump,
@@ -117,38 +128,120 @@ pub struct Swap<'info> {
     )]
     user_no_ata: AccountInfo<'info>,
 
+    //  only needed for whitelisted users or to activate an entry from a merkle proof,
+    //  users without it pay standard fees
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + std::mem::size_of::<Whitelist>(),
+        space = 8 + Whitelist::INIT_SPACE,
         seeds = [WHITELIST.as_bytes(), &user.key().to_bytes()],
         bump
     )]
-    pub whitelist: Account<'info, Whitelist>,
+    pub whitelist: Option<Account<'info, Whitelist>>,
+
+    //  tracks holdings and cost basis of the user, required when the market has position limits
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [POSITION.as_bytes(), &market.key().to_bytes(), &user.key().to_bytes()],
+        bump
+    )]
+    pub position: Option<Account<'info, Position>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
+    //  spl collateral accounts, only passed for markets whose collateral is not native sol
+    pub collateral_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = global_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub global_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub user_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = team_wallet,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub team_wallet_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = team_wallet2,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub team_wallet2_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = creator_vault,
+        associated_token::token_program = collateral_token_program,
+    )]
+    pub creator_vault_collateral_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub collateral_token_program: Option<Interface<'info, TokenInterface>>,
+
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     #[account(address = associated_token::ID)]
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> Swap<'info> { 
-pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8, minimum_receive_amount: u64,global_vault_bump:u8) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8, minimum_receive_amount: u64, whitelist_proof: Option<WhitelistProof>, global_vault_bump:u8, market_vault_bump: u8) -> Result<()> {
 
     msg!("amount: {:?}, direction: {:?}, token_type: {:?}, minimum_receive_amount: {:?}", amount, direction, token_type, minimum_receive_amount);
+    let market_key = self.market.key();
+    let market_vault_seeds: &[&[&[u8]]] = &[&[
+        MARKET_VAULT.as_bytes(),
+        market_key.as_ref(),
+        &[market_vault_bump],
+    ]];
+    let global_vault_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+    let collateral = Collateral::new(
+        self.market.collateral_mint,
+        Vault {
+            account: self.market_vault.to_account_info(),
+            signer_seeds: market_vault_seeds,
+        },
+        Vault {
+            account: self.global_vault.to_account_info(),
+            signer_seeds: global_vault_seeds,
+        },
+        &self.system_program,
+        self.collateral_token(),
+    )?;
     let market = &mut self.market;
 
     let source = &mut self.global_vault.to_account_info();
-    let creator_vault = &mut self.creator_vault.to_account_info();
-    let team_wallet = &mut self.team_wallet;
-    let team_wallet2 = &mut self.team_wallet2;
+    //  fees are paid to the collateral atas of the wallets on spl collateral markets
+    let creator_vault = &mut collateral.account(&self.creator_vault, self.creator_vault_collateral_ata.as_ref().map(|ata| ata.to_account_info()))?;
+    let team_wallet = &mut collateral.account(&self.team_wallet, self.team_wallet_collateral_ata.as_ref().map(|ata| ata.to_account_info()))?;
+    let team_wallet2 = &mut collateral.account(&self.team_wallet2, self.team_wallet2_collateral_ata.as_ref().map(|ata| ata.to_account_info()))?;
 
     let yes_token = &mut self.yes_token;
     let user_yes_ata = &mut self.user_yes_ata;
@@ -156,23 +249,14 @@ pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8, minimum_re
     let no_token = &mut self.no_token;
     let user_no_ata = &mut self.user_no_ata;
 
-    let whitelist = &mut self.whitelist;
-
     let current_timestamp = Clock::get()?.unix_timestamp;
-    if whitelist.is_allow == 1 && whitelist.first_swap_timestamp == 0
-    {
-        whitelist.first_swap_timestamp = current_timestamp;
-    }
-
-    let is_small_fee: bool;
-    if whitelist.is_allow == 1
-    {
-        is_small_fee = whitelist.is_whitelister(self.global_config.limit_timestamp, current_timestamp)?;
-    }
-    else
-    {
-        is_small_fee = false;
-    }
+    let (fee_tier, is_whitelisted) = Whitelist::swap_access(
+        self.whitelist.as_deref_mut(),
+        self.user.key(),
+        whitelist_proof.as_ref(),
+        &self.global_config,
+        current_timestamp,
+    )?;
 
     if token_type == 0{
         //  create user wallet no ata, if it doesn't exit
@@ -214,13 +298,13 @@ pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8, minimum_re
     ]];
 
      market.swap(
-        &*self.global_config,
+        &self.global_config,
 
-        yes_token.as_ref(),
+        &yes_token.to_account_info(),
         &mut self.global_yes_ata,
         user_yes_ata,
 
-        no_token.as_ref(),
+        &no_token.to_account_info(),
         &mut self.global_no_ata,
         user_no_ata,
 
@@ -237,13 +321,26 @@ pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8, minimum_re
         &self.user,
         signer_seeds,
 
-        is_small_fee,
+        fee_tier,
+        is_whitelisted,
+        self.position.as_mut(),
 
         &self.token_program,
-        &self.system_program,
+        &collateral,
     )?;
 
     Ok(())
 }
 
+    fn collateral_token(&self) -> Option<CollateralToken<'info>> {
+        let mint = self.collateral_mint.as_ref()?;
+        Some(CollateralToken {
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+            token_program: self.collateral_token_program.as_ref()?.to_account_info(),
+            vault_ata: self.global_collateral_ata.as_ref()?.to_account_info(),
+            user_ata: self.user_collateral_ata.as_ref()?.to_account_info(),
+        })
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,199 @@
+use crate::{
+    constants::{CONFIG, CREATOR, GLOBAL, MARKET_VAULT, POSITION, WHITELIST},
+    errors::*,
+    state::{categorical_market::*, config::*, position::*, whitelist::*},
+    utils::{Collateral, Vault},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_interface::{Mint, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct SwapCategorical<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// CHECK: should be same with the address in the global_config
+    #[account(
+        mut,
+        constraint = global_config.team_wallet == team_wallet.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub team_wallet: AccountInfo<'info>,
+
+    /// CHECK: should be same with the address in the global_config
+    #[account(
+        mut,
+        constraint = global_config.team_wallet2 == team_wallet2.key() @TakesFunError::IncorrectAuthority
+    )]
+    pub team_wallet2: AccountInfo<'info>,
+
+    #[account(mut)]
+    market: Box<Account<'info, CategoricalMarket>>,
+
+    /// CHECK: global vault pda which owns the outcome atas
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// CHECK: market vault pda which stores the SOL reserves of the market
+    #[account(
+        mut,
+        seeds = [MARKET_VAULT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    /// CHECK: CREATOR vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [CREATOR.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub creator_vault: AccountInfo<'info>,
+
+    //  checked against the market outcomes in CategoricalMarketAccount::swap
+    //  soulbound mints are minted and burned by the swap
+    #[account(mut, mint::token_program = token_program)]
+    pub outcome_token: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: outcome ata of global vault
+    #[account(
+        mut,
+        seeds = [
+            global_vault.key().as_ref(),
+            token_program.key().as_ref(),
+            outcome_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    global_outcome_ata: AccountInfo<'info>,
+
+    /// CHECK: ata of user
+    #[account(
+        mut,
+        seeds = [
+            user.key().as_ref(),
+            token_program.key().as_ref(),
+            outcome_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    user_outcome_ata: AccountInfo<'info>,
+
+    //  only needed for whitelisted users or to activate an entry from a merkle proof,
+    //  users without it pay standard fees
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Whitelist::INIT_SPACE,
+        seeds = [WHITELIST.as_bytes(), &user.key().to_bytes()],
+        bump
+    )]
+    pub whitelist: Option<Account<'info, Whitelist>>,
+
+    //  tracks holdings and cost basis of the user, required when the market has position limits
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + CategoricalPosition::INIT_SPACE,
+        seeds = [POSITION.as_bytes(), &market.key().to_bytes(), &user.key().to_bytes()],
+        bump
+    )]
+    pub position: Option<Account<'info, CategoricalPosition>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> SwapCategorical<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn handler(
+        &mut self,
+        outcome: u8,
+        amount: u64,
+        direction: u8,
+        minimum_receive_amount: u64,
+        whitelist_proof: Option<WhitelistProof>,
+        global_vault_bump: u8,
+        market_vault_bump: u8,
+    ) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let (fee_tier, is_whitelisted) = Whitelist::swap_access(
+            self.whitelist.as_deref_mut(),
+            self.user.key(),
+            whitelist_proof.as_ref(),
+            &self.global_config,
+            current_timestamp,
+        )?;
+
+        //  create user wallet outcome ata, if it doesn't exit
+        if self.user_outcome_ata.data_is_empty() {
+            associated_token::create(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: self.user.to_account_info(),
+                    associated_token: self.user_outcome_ata.to_account_info(),
+                    authority: self.user.to_account_info(),
+                    mint: self.outcome_token.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                },
+            ))?;
+        }
+
+        let market_key = self.market.key();
+        let market_vault_seeds: &[&[&[u8]]] = &[&[
+            MARKET_VAULT.as_bytes(),
+            market_key.as_ref(),
+            &[market_vault_bump],
+        ]];
+        //  categorical markets trade native sol only
+        let collateral = Collateral::Sol {
+            vault: Vault {
+                account: self.market_vault.to_account_info(),
+                signer_seeds: market_vault_seeds,
+            },
+            system_program: &self.system_program,
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        self.market.swap(
+            &self.global_config,
+            outcome,
+            &self.outcome_token,
+            &mut self.global_outcome_ata,
+            &mut self.user_outcome_ata,
+            &mut self.global_vault,
+            &mut self.team_wallet,
+            &mut self.team_wallet2,
+            &mut self.creator_vault,
+            amount,
+            direction,
+            minimum_receive_amount,
+            &self.user,
+            signer_seeds,
+            fee_tier,
+            is_whitelisted,
+            self.position.as_mut(),
+            &self.token_program,
+            &collateral,
+        )
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    constants::{CONFIG, MARKET_METADATA},
+    errors::*,
+    events::MarketMetadataEvent,
+    state::{config::*, market::*, market_metadata::*},
+};
+use anchor_lang::{prelude::*, system_program};
+
+#[derive(Accounts)]
+pub struct UpdateMarketMetadata<'info> {
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    // Market creator or curator
+    #[account(
+        mut,
+        constraint = market.creator == *authority.key
+            || global_config.curator == *authority.key @TakesFunError::IncorrectAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    market: Box<Account<'info, Market>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MarketMetadata::INIT_SPACE,
+        seeds = [MARKET_METADATA.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    market_metadata: Box<Account<'info, MarketMetadata>>,
+
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl UpdateMarketMetadata<'_> {
+    pub fn handler(&mut self, params: MarketMetadataParams) -> Result<()> {
+        //  traders bought on the published description, it is fixed from then on
+        require!(
+            self.market.first_trade_at == 0,
+            TakesFunError::MarketAlreadyTraded
+        );
+        params.validate()?;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let metadata = &mut self.market_metadata;
+        metadata.market = self.market.key();
+        metadata.question = params.question;
+        metadata.category = params.category;
+        metadata.tags = params.tags;
+        metadata.resolution_source = params.resolution_source;
+        metadata.resolution_criteria = params.resolution_criteria;
+        metadata.updated_at = timestamp;
+
+        emit!(MarketMetadataEvent {
+            authority: self.authority.key(),
+            market: metadata.market,
+            question: metadata.question.clone(),
+            category: metadata.category.clone(),
+            tags: metadata.tags.clone(),
+            resolution_source: metadata.resolution_source.clone(),
+            resolution_criteria: metadata.resolution_criteria.clone(),
+            timestamp,
+        });
+        Ok(())
+    }
+}
@@ -7,24 +7,72 @@ pub mod state;
 pub mod utils;
 
 use instructions::{
-    accept_authority::*, add_wl::*, change_creator::*, configure::*, create_market::*,
-    create_market_second::*, creator_claim::*, creator_claim_second::*, mint_no_token::*,
-    nominate_authority::*, swap::*, swap_second::*,
+    accept_authority::*, accept_creator::*, accept_role::*, add_categorical_outcome::*, add_wl::*,
+    audit_market::*, cancel_config_change::*, challenge_resolution::*, change_creator::*,
+    close_market::*, close_wl::*, configure::*, create_categorical_market::*, create_market::*,
+    create_market_second::*, creator_claim::*, creator_claim_categorical::*,
+    creator_claim_collateral::*, creator_claim_second::*, execute_config_change::*,
+    finalize_resolution::*, migrate::*, migrate_categorical_market::*,
+    migrate_categorical_market_vault::*, migrate_config::*, migrate_creator_vault::*,
+    migrate_market::*, migrate_market_vault::*, migrate_whitelist::*, mint_no_token::*,
+    nominate_authority::*, nominate_creator::*, nominate_role::*, redeem::*, remove_wl::*,
+    resolve_market::*, schedule_config_change::*, set_market_frozen::*, set_market_limits::*,
+    set_paused::*, set_whitelist_root::*, swap::*, swap_categorical::*, swap_second::*,
+    update_market_metadata::*,
+};
+use state::{
+    config::*,
+    market::{InitialBuy, ScalarBounds},
+    market_metadata::MarketMetadataParams,
+    whitelist::WhitelistProof,
 };
-use state::config::*;
 
 declare_id!("4D1RaYpBgEAj437RBaCkbKkpN2S2BA4CcmkE35MR1CZv");
 #[program]
 pub mod takesfun {
     use super::*;
 
-    //  called by admin to set global config
-    //  need to check the signer is authority
+    //  called by admin to create global config, only once
     pub fn configure(ctx: Context<Configure>, new_config: Config) -> Result<()> {
         msg!("configure: {:#?}", new_config);
         ctx.accounts.handler(new_config, ctx.bumps.config)
     }
 
+    //  called by admin to move a config created by an older program version to the
+    //  current layout, new roles default to the holders of the old ones
+    pub fn migrate_config(ctx: Context<MigrateConfig>, timelock_delay: i64) -> Result<()> {
+        ctx.accounts.process(timelock_delay)
+    }
+
+    //  Admin can queue a partial config update, executable after Config::timelock_delay
+    pub fn schedule_config_change(
+        ctx: Context<ScheduleConfigChange>,
+        change: ConfigUpdate,
+        execute_after: i64,
+    ) -> Result<()> {
+        ctx.accounts.process(change, execute_after)
+    }
+
+    //  Anyone can execute a queued config update once its timelock has passed
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    //  Admin can cancel a queued config update
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    //  Admin can hand over the guardian, curator and whitelist manager roles
+    pub fn nominate_role(ctx: Context<NominateRole>, role: Role, new_holder: Pubkey) -> Result<()> {
+        ctx.accounts.process(role, new_holder)
+    }
+
+    //  Pending role holder should accept the role
+    pub fn accept_role(ctx: Context<AcceptRole>, role: Role) -> Result<()> {
+        ctx.accounts.process(role)
+    }
+
     //  Admin can hand over admin role
     pub fn nominate_authority(ctx: Context<NominateAuthority>, new_admin: Pubkey) -> Result<()> {
         ctx.accounts.process(new_admin)
@@ -35,16 +83,72 @@ pub mod takesfun {
         ctx.accounts.process()
     }
 
+    //  Admin or guardian can pause create / swap / claim for the whole program,
+    //  see Config::PAUSE_*, only admin can lift a pause
+    pub fn set_paused(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+        ctx.accounts.process(paused)
+    }
+
+    //  Admin or guardian can freeze trading and claiming on a single market,
+    //  only admin can unfreeze
+    pub fn set_market_frozen(ctx: Context<SetMarketFrozen>, frozen: bool) -> Result<()> {
+        ctx.accounts.process(frozen)
+    }
+
+    //  Admin or curator can override the per wallet limits of a market, 0 disables a limit
+    pub fn set_market_limits(
+        ctx: Context<SetMarketLimits>,
+        max_wallet_sol: u64,
+        max_wallet_share_bps: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(max_wallet_sol, max_wallet_share_bps)
+    }
+
+    //  Admin or curator moves the SOL of a legacy market from the global vault
+    //  into its own market vault, trading and redeeming require the market vault.
+    //  each market gets its pro rata share of Config::legacy_sol_liabilities
+    pub fn migrate_market_vault(ctx: Context<MigrateMarketVault>) -> Result<()> {
+        ctx.accounts.process(ctx.bumps.global_vault)
+    }
+
+    //  Same as migrate_market_vault for a categorical market
+    pub fn migrate_categorical_market_vault(
+        ctx: Context<MigrateCategoricalMarketVault>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.bumps.global_vault)
+    }
+
     pub fn create_market(
         ctx: Context<CreateMarket>,
         // metadata
         yes_symbol: String,
         yes_uri: String,
+        //  non transferable outcome tokens, the no token has to be minted the same way
+        soulbound: bool,
 
         market_info: String,
+
+        //  bounds of a scalar market, None creates a binary market
+        scalar_bounds: Option<ScalarBounds>,
+        //  optional buy of the creator in the same transaction
+        initial_buy: Option<InitialBuy>,
     ) -> Result<()> {
+        ctx.accounts.handler(
+            yes_symbol,
+            yes_uri,
+            soulbound,
+            market_info,
+            scalar_bounds,
+            initial_buy,
+            ctx.bumps.global_vault,
+            ctx.bumps.market_vault,
+        )
+    }
+
+    //  Migration authority moves a market with a completed side into amm pools
+    pub fn migrate(ctx: Context<Migrate>) -> Result<()> {
         ctx.accounts
-            .handler(yes_symbol, yes_uri, market_info, ctx.bumps.global_vault)
+            .handler(ctx.bumps.global_vault, ctx.bumps.market_vault)
     }
 
     pub fn mint_no_token(
@@ -52,51 +156,90 @@ pub mod takesfun {
         // metadata
         no_symbol: String,
         no_uri: String,
+        soulbound: bool,
     ) -> Result<()> {
         ctx.accounts
-            .handler(no_symbol, no_uri, ctx.bumps.global_vault)
+            .handler(no_symbol, no_uri, soulbound, ctx.bumps.global_vault)
     }
 
     //  amount - swap amount
     //  direction - 0: buy, 1: sell
+    //  whitelist_proof - activates the user's whitelist entry from the config merkle root
     pub fn swap(
         ctx: Context<Swap>,
         amount: u64,
         direction: u8,
         token_type: u8,
         minimum_receive_amount: u64,
+        whitelist_proof: Option<WhitelistProof>,
     ) -> Result<()> {
         ctx.accounts.handler(
             amount,
             direction,
             token_type,
             minimum_receive_amount,
+            whitelist_proof,
             ctx.bumps.global_vault,
+            ctx.bumps.market_vault,
         )
     }
 
-    pub fn add_wl(ctx: Context<AddWl>, new_whitelister: Pubkey) -> Result<()> {
-        ctx.accounts.handler(new_whitelister)
+    //  tier - fee tier, see Config::fee_tiers
+    //  expiry - timestamp the entry stops applying, 0 means no expiry
+    pub fn add_wl(
+        ctx: Context<AddWl>,
+        new_whitelister: Pubkey,
+        tier: u8,
+        expiry: i64,
+    ) -> Result<()> {
+        ctx.accounts.handler(new_whitelister, tier, expiry)
+    }
+
+    //  Admin can publish the merkle root of whitelisted (user, tier, expiry) leaves
+    pub fn set_whitelist_root(ctx: Context<SetWhitelistRoot>, root: [u8; 32]) -> Result<()> {
+        ctx.accounts.process(root)
+    }
+
+    pub fn remove_wl(ctx: Context<RemoveWl>, whitelister: Pubkey) -> Result<()> {
+        ctx.accounts.handler(whitelister)
+    }
+
+    //  move a whitelist entry created before tiers and expiry to the current layout
+    pub fn migrate_whitelist(ctx: Context<MigrateWhitelist>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    //  reclaim the rent of an inactive whitelist entry
+    pub fn close_whitelist(ctx: Context<CloseWl>) -> Result<()> {
+        ctx.accounts.handler()
     }
 
     pub fn creator_claim(ctx: Context<CreeatorClaim>) -> Result<()> {
         ctx.accounts.handler(ctx.bumps.creator_vault)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market_second(
         ctx: Context<CreateMarketSecond>, // metadata
         market_info: String,
         yes_symbol: String,
         yes_uri: String,
+        soulbound: bool,
         creator_wallet: Pubkey,
+        scalar_bounds: Option<ScalarBounds>,
+        initial_buy: Option<InitialBuy>,
     ) -> Result<()> {
         msg!("create_market_second: {:#?}", market_info);
         ctx.accounts.handler(
             market_info,
             yes_symbol,
             yes_uri,
+            soulbound,
             creator_wallet,
+            scalar_bounds,
+            initial_buy,
             ctx.bumps.global_vault,
+            ctx.bumps.market_vault,
         )
     }
 
@@ -109,6 +252,7 @@ pub mod takesfun {
         direction: u8,
         token_type: u8,
         minimum_receive_amount: u64,
+        whitelist_proof: Option<WhitelistProof>,
     ) -> Result<()> {
         ctx.accounts.handler(
             market_info,
@@ -116,7 +260,9 @@ pub mod takesfun {
             direction,
             token_type,
             minimum_receive_amount,
+            whitelist_proof,
             ctx.bumps.global_vault,
+            ctx.bumps.market_vault,
         )
     }
 
@@ -127,6 +273,17 @@ pub mod takesfun {
         ctx.accounts.handler(market_info, ctx.bumps.creator_vault)
     }
 
+    //  Creator can hand over the creator role of a market
+    pub fn nominate_creator(ctx: Context<NominateCreator>, new_creator: Pubkey) -> Result<()> {
+        ctx.accounts.process(new_creator)
+    }
+
+    //  Pending creator should accept the creator role
+    pub fn accept_creator(ctx: Context<AcceptCreator>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    //  Backend signer can override the creator of a market
     pub fn change_creator(
         ctx: Context<ChangeCreator>,
         market_info: String,
@@ -134,4 +291,131 @@ pub mod takesfun {
     ) -> Result<()> {
         ctx.accounts.handler(market_info, new_creator)
     }
+
+    //  move creator fees stranded in a vault seeded by a previous creator key
+    pub fn migrate_creator_vault(
+        ctx: Context<MigrateCreatorVault>,
+        old_creator: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(old_creator, ctx.bumps.old_creator_vault)
+    }
+
+    //  categorical market with outcome_count outcomes, each added by add_categorical_outcome
+    pub fn create_categorical_market(
+        ctx: Context<CreateCategoricalMarket>,
+        market_info: String,
+        outcome_count: u8,
+        //  non transferable outcome tokens
+        soulbound: bool,
+    ) -> Result<()> {
+        ctx.accounts.handler(market_info, outcome_count, soulbound)
+    }
+
+    pub fn add_categorical_outcome(
+        ctx: Context<AddCategoricalOutcome>,
+        // metadata
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(name, symbol, uri, ctx.bumps.global_vault)
+    }
+
+    //  outcome - index of the traded outcome
+    //  direction - 0: buy, 1: sell
+    pub fn swap_categorical(
+        ctx: Context<SwapCategorical>,
+        outcome: u8,
+        amount: u64,
+        direction: u8,
+        minimum_receive_amount: u64,
+        whitelist_proof: Option<WhitelistProof>,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            outcome,
+            amount,
+            direction,
+            minimum_receive_amount,
+            whitelist_proof,
+            ctx.bumps.global_vault,
+            ctx.bumps.market_vault,
+        )
+    }
+
+    //  Anyone can upgrade a categorical market created before position limits,
+    //  the caller pays the rent of the extra space
+    pub fn migrate_categorical_market(ctx: Context<MigrateCategoricalMarket>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    pub fn creator_claim_categorical(ctx: Context<CreatorClaimCategorical>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.creator_vault)
+    }
+
+    //  creator claims the fees of an spl collateral market
+    pub fn creator_claim_collateral(ctx: Context<CreatorClaimCollateral>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.creator_vault)
+    }
+
+    //  Anyone can close a migrated market, or a resolved one once every winning token is
+    //  redeemed or the grace period is over, rent of the market and its metadata goes back
+    //  to the creation payer, unclaimed creator fees to the creator, and the closer pays for
+    //  a tombstone that keeps the market from being created again
+    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+        ctx.accounts.handler(
+            ctx.bumps.global_vault,
+            ctx.bumps.market_vault,
+            ctx.bumps.creator_vault,
+        )
+    }
+
+    //  Anyone can upgrade a market created with an older account layout,
+    //  the caller pays the rent of the extra space
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    //  Creator or curator sets the question, category, tags and resolution criteria
+    //  of a market, only possible before its first trade
+    pub fn update_market_metadata(
+        ctx: Context<UpdateMarketMetadata>,
+        params: MarketMetadataParams,
+    ) -> Result<()> {
+        ctx.accounts.handler(params)
+    }
+
+    //  Anyone can compare the recorded reserves of a market with the vault holdings,
+    //  the market liabilities are reconciled into Config::total_sol_liabilities
+    pub fn audit_market(ctx: Context<AuditMarket>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    //  Admin or curator proposes the value a market settles on, binary markets resolve to
+    //  0 (NO) or 1 (YES), trading stops until the proposal is challenged or finalized
+    pub fn resolve_market(ctx: Context<ResolveMarket>, value: i64) -> Result<()> {
+        ctx.accounts.process(value)
+    }
+
+    //  Admin or guardian drops a proposed resolution during its challenge period
+    pub fn challenge_resolution(ctx: Context<ChallengeResolution>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    //  Anyone applies a proposed resolution once RESOLUTION_CHALLENGE_PERIOD passed
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    //  burn outcome tokens of a resolved market for their share of the payout pool
+    //  token_type - 0: no, 1: yes
+    pub fn redeem(ctx: Context<Redeem>, token_type: u8, amount: u64) -> Result<()> {
+        ctx.accounts.handler(
+            token_type,
+            amount,
+            ctx.bumps.global_vault,
+            ctx.bumps.market_vault,
+        )
+    }
 }
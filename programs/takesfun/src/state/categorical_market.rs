@@ -0,0 +1,420 @@
+use crate::errors::*;
+use crate::events::*;
+use crate::state::config::*;
+use crate::state::curve::*;
+use crate::state::position::{CategoricalPosition, PositionLimits};
+use crate::utils::*;
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+pub const MAX_OUTCOMES: usize = 8;
+pub const MAX_CATEGORICAL_INFO_LEN: usize = 200;
+
+//  curve of a single outcome, same math as the yes / no curves of Market
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Default, Debug)]
+pub struct Outcome {
+    pub mint: Pubkey,
+    pub curve: Curve,
+}
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct CategoricalMarket {
+    pub creator: Pubkey,
+
+    //  outcomes are added one per instruction, trading starts once all are created
+    pub outcome_count: u8,
+    #[max_len(MAX_OUTCOMES)]
+    pub outcomes: Vec<Outcome>,
+
+    pub is_completed: bool,
+    pub frozen: bool,
+    pub created_at: i64,
+
+    #[max_len(MAX_CATEGORICAL_INFO_LEN)]
+    pub market_info: String,
+
+    //  per wallet limits and launch phase, copied from the config on creation,
+    //  0 means no limit
+    pub max_wallet_sol: u64,
+    pub max_wallet_share_bps: u64,
+    pub launch_phase: LaunchPhase,
+
+    //  bit i is set once the real SOL reserves of outcome i reach
+    //  Config::graduation_threshold, trading of that outcome stops
+    pub completed_outcomes: u8,
+
+    //  SOL reserves sit in the market vault pda, markets created before it keep them in the
+    //  global vault until migrate_categorical_market_vault
+    pub has_market_vault: bool,
+
+    //  outcome mints are non transferable, minted on buys and burned on sells
+    pub soulbound: bool,
+}
+
+impl CategoricalMarket {
+    pub fn is_ready(&self) -> bool {
+        self.outcomes.len() == self.outcome_count as usize
+    }
+
+    //  SOL still owed to traders, the real reserves of all outcome curves
+    pub fn sol_liabilities(&self) -> Option<u64> {
+        self.outcomes.iter().try_fold(0u64, |total, outcome| {
+            total.checked_add(outcome.curve.real_sol_reserves)
+        })
+    }
+
+    //  scales the outcome reserves down to the SOL the market received from the global
+    //  vault, see Market::write_down_sol_liabilities
+    pub fn write_down_sol_liabilities(&mut self, paid: u64, owed: u64) -> Result<()> {
+        if paid >= owed {
+            return Ok(());
+        }
+        for outcome in self.outcomes.iter_mut() {
+            outcome.curve.real_sol_reserves = mul_div(outcome.curve.real_sol_reserves, paid, owed)
+                .ok_or(TakesFunError::ArithmeticError)?;
+        }
+        Ok(())
+    }
+
+    pub fn position_limits(&self) -> PositionLimits {
+        PositionLimits {
+            max_wallet_sol: self.max_wallet_sol,
+            max_wallet_share_bps: self.max_wallet_share_bps,
+        }
+    }
+
+    pub fn is_outcome_completed(&self, outcome: usize) -> bool {
+        self.completed_outcomes & (1 << outcome) != 0
+    }
+
+    //  buying an outcome takes virtual SOL from all the other outcomes, split evenly,
+    //  and releases the real SOL they no longer need, returns (virtual, real) shifts
+    pub fn apply_cross_effect(
+        &mut self,
+        outcome: usize,
+        sol_amount: u64,
+        global_config: &Config,
+    ) -> Option<(u64, u64)> {
+        let shift_per_outcome = global_config
+            .cross_shift(sol_amount)
+            .checked_div(self.outcomes.len() as u64 - 1)?;
+
+        let mut shift_lamports_virtual: u64 = 0;
+        let mut shift_lamports_real: u64 = 0;
+        for (index, other) in self.outcomes.iter_mut().enumerate() {
+            if index == outcome {
+                continue;
+            }
+
+            let (virtual_shift, real_shift) = other.curve.remove_liquidity(
+                shift_per_outcome,
+                global_config.initial_virtual_yes_token_reserves_config,
+                global_config.min_sol_liquidity,
+            )?;
+            shift_lamports_virtual = shift_lamports_virtual.checked_add(virtual_shift)?;
+            shift_lamports_real = shift_lamports_real.checked_add(real_shift)?;
+        }
+
+        Some((shift_lamports_virtual, shift_lamports_real))
+    }
+}
+
+pub trait CategoricalMarketAccount<'info> {
+    #[allow(clippy::too_many_arguments)]
+    fn swap(
+        &mut self,
+        global_config: &Account<'info, Config>,
+
+        outcome: u8,
+        mint: &InterfaceAccount<'info, Mint>,
+        global_ata: &mut AccountInfo<'info>,
+        user_ata: &mut AccountInfo<'info>,
+
+        source: &mut AccountInfo<'info>,
+        team_wallet: &mut AccountInfo<'info>,
+        team_wallet2: &mut AccountInfo<'info>,
+        creator_vault: &mut AccountInfo<'info>,
+
+        amount: u64,
+        direction: u8,
+        minimum_receive_amount: u64,
+
+        user: &Signer<'info>,
+        signer: &[&[&[u8]]],
+
+        fee_tier: Option<u8>,
+        is_whitelisted: bool,
+        position: Option<&mut Account<'info, CategoricalPosition>>,
+
+        token_program: &Interface<'info, TokenInterface>,
+        collateral: &Collateral<'_, 'info>,
+    ) -> Result<()>;
+}
+
+impl<'info> CategoricalMarketAccount<'info> for Account<'info, CategoricalMarket> {
+    fn swap(
+        &mut self,
+        global_config: &Account<'info, Config>,
+
+        outcome: u8,
+        mint: &InterfaceAccount<'info, Mint>,
+        global_ata: &mut AccountInfo<'info>,
+        user_ata: &mut AccountInfo<'info>,
+
+        source: &mut AccountInfo<'info>,
+        team_wallet: &mut AccountInfo<'info>,
+        team_wallet2: &mut AccountInfo<'info>,
+        creator_vault: &mut AccountInfo<'info>,
+
+        amount: u64,
+        direction: u8,
+        minimum_receive_amount: u64,
+
+        user: &Signer<'info>,
+        signer: &[&[&[u8]]],
+
+        fee_tier: Option<u8>,
+        is_whitelisted: bool,
+        position: Option<&mut Account<'info, CategoricalPosition>>,
+
+        token_program: &Interface<'info, TokenInterface>,
+        collateral: &Collateral<'_, 'info>,
+    ) -> Result<()> {
+        let index = outcome as usize;
+        require!(
+            !global_config.is_paused(Config::PAUSE_SWAP),
+            TakesFunError::ProgramPaused
+        );
+        require!(!self.frozen, TakesFunError::MarketFrozen);
+        require!(
+            !self.is_completed && !self.is_outcome_completed(index),
+            TakesFunError::MarketAlreadyCompleted
+        );
+        require!(self.is_ready(), TakesFunError::OutcomesNotCreated);
+        require!(self.has_market_vault, TakesFunError::MarketVaultNotMigrated);
+        require!(amount > 0, TakesFunError::InvalidAmount);
+        require!(
+            self.outcomes
+                .get(index)
+                .is_some_and(|o| o.mint == mint.key()),
+            TakesFunError::InvalidOutcome
+        );
+
+        let mut curve = self.outcomes[index].curve;
+        curve
+            .check_update_real_sol_reserves(global_config.initial_virtual_yes_token_reserves_config)
+            .ok_or(TakesFunError::InsufficientRealSolReserves)?;
+        let side = SwapSide::new(mint.to_account_info(), global_ata.clone(), user_ata.clone())?;
+        let side_supply = side.supply(global_config);
+        let swap_result = curve.swap(
+            global_config,
+            side,
+            source,
+            team_wallet,
+            creator_vault,
+            amount,
+            direction,
+            minimum_receive_amount,
+            fee_tier,
+            user,
+            signer,
+            token_program,
+            collateral,
+        )?;
+        self.outcomes[index].curve = curve;
+
+        let mut shift_lamports_virtual: u64 = 0;
+        let mut shift_lamports_real: u64 = 0;
+        if direction != 1 {
+            (shift_lamports_virtual, shift_lamports_real) = self
+                .apply_cross_effect(index, swap_result.sol_amount, global_config)
+                .ok_or(TakesFunError::InvalidExpectedRealSolReserves)?;
+            msg!(
+                "shift virtual: {}, shift real: {}",
+                shift_lamports_virtual,
+                shift_lamports_real
+            );
+
+            if shift_lamports_real > 0 {
+                collateral.transfer_from_vault(team_wallet2.clone(), shift_lamports_real)?;
+            }
+        }
+
+        let wallet_sol_spent = match position {
+            Some(position) => {
+                if position.user == Pubkey::default() {
+                    position.market = self.key();
+                    position.user = user.key();
+                }
+                position.apply_swap(
+                    self,
+                    direction,
+                    index,
+                    swap_result.sol_amount,
+                    swap_result.token_amount,
+                    swap_result.fee_lamports(),
+                    side_supply,
+                    token_balance(user_ata)?,
+                )?;
+                Some(position.total_sol_spent)
+            }
+            None => {
+                require!(
+                    !self.position_limits().is_limited(),
+                    TakesFunError::MissingPositionAccount
+                );
+                None
+            }
+        };
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        if direction != 1 {
+            self.launch_phase.check(
+                self.created_at,
+                timestamp,
+                amount,
+                is_whitelisted,
+                wallet_sol_spent,
+            )?;
+        }
+
+        let real_sol_reserves = self.outcomes[index].curve.real_sol_reserves;
+        let completed = direction != 1 && global_config.is_graduated(real_sol_reserves);
+        if completed {
+            self.completed_outcomes |= 1 << index;
+        }
+
+        emit!(CategoricalTradeEvent {
+            user: user.key(),
+            market: self.key(),
+            mint: mint.key(),
+
+            sol_amount: swap_result.sol_amount,
+            token_amount: swap_result.token_amount,
+            platform_fee_lamports: swap_result.platform_fee_lamports,
+            creator_fee_lamports: swap_result.creator_fee_lamports,
+            shift_lamports_real,
+            shift_lamports_virtual,
+
+            direction,
+            outcome,
+            timestamp,
+
+            outcomes: self.outcomes.clone(),
+        });
+
+        //  token_type is the outcome index on categorical markets
+        if completed {
+            emit!(CompleteEvent {
+                market: self.key(),
+                token_type: outcome,
+                real_sol_reserves,
+                timestamp,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::config::tests::test_config;
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn test_categorical_market(outcome_count: u8, config: &Config) -> CategoricalMarket {
+        let data = vec![0u8; CategoricalMarket::INIT_SPACE];
+        let mut market = CategoricalMarket::try_deserialize_unchecked(
+            &mut [&[0u8; 8][..], &data].concat().as_slice(),
+        )
+        .unwrap();
+        market.outcome_count = outcome_count;
+        for _ in 0..outcome_count {
+            market.outcomes.push(Outcome {
+                mint: Pubkey::new_unique(),
+                curve: Curve {
+                    virtual_sol_reserves: config.initial_virtual_yes_sol_reserves_config,
+                    virtual_token_reserves: config.initial_virtual_yes_token_reserves_config,
+                    real_sol_reserves: 0,
+                    real_token_reserves: config.initial_real_yes_token_reserves_config,
+                },
+            });
+        }
+        market
+    }
+
+    #[test]
+    fn cross_effect_splits_the_shift_between_the_other_outcomes() {
+        let config = test_config();
+        let mut market = test_categorical_market(3, &config);
+        let bought = market.outcomes[0].curve;
+
+        //  cross_sol_factor 0.5 moves 1 SOL, half of it from each other outcome
+        let (shift_virtual, shift_real) = market.apply_cross_effect(0, 2 * SOL, &config).unwrap();
+        assert_eq!(shift_virtual, SOL);
+        assert_eq!(shift_real, 0);
+        assert_eq!(
+            market.outcomes[0].curve.virtual_sol_reserves,
+            bought.virtual_sol_reserves
+        );
+        for other in &market.outcomes[1..] {
+            assert_eq!(
+                other.curve.virtual_sol_reserves,
+                config.initial_virtual_yes_sol_reserves_config - SOL / 2
+            );
+        }
+    }
+
+    #[test]
+    fn cross_effect_keeps_min_sol_liquidity() {
+        let config = test_config();
+        let mut market = test_categorical_market(2, &config);
+        market.outcomes[1].curve.virtual_sol_reserves = config.min_sol_liquidity + SOL / 5;
+
+        let (shift_virtual, _) = market.apply_cross_effect(0, 2 * SOL, &config).unwrap();
+        assert_eq!(shift_virtual, SOL / 5);
+        assert_eq!(
+            market.outcomes[1].curve.virtual_sol_reserves,
+            config.min_sol_liquidity
+        );
+    }
+
+    #[test]
+    fn cross_effect_releases_real_sol_of_the_other_outcomes() {
+        let config = test_config();
+        let mut market = test_categorical_market(3, &config);
+        market.outcomes[1].curve.apply_buy(5 * SOL).unwrap();
+        let liabilities_before = market.sol_liabilities().unwrap();
+
+        let (_, shift_real) = market.apply_cross_effect(0, 4 * SOL, &config).unwrap();
+        assert!(shift_real > 0);
+        assert_eq!(
+            market.sol_liabilities().unwrap(),
+            liabilities_before - shift_real
+        );
+
+        //  what is left still backs the tokens the curve sold
+        let curve = market.outcomes[1].curve;
+        let expected_real_sol_reserves = curve
+            .expected_real_sol_reserves(config.initial_virtual_yes_token_reserves_config)
+            .unwrap();
+        assert_eq!(
+            curve.real_sol_reserves,
+            expected_real_sol_reserves.max(config.min_sol_liquidity)
+        );
+        assert_eq!(market.outcomes[2].curve.real_sol_reserves, 0);
+    }
+
+    #[test]
+    fn outcomes_complete_one_by_one() {
+        let config = test_config();
+        let mut market = test_categorical_market(3, &config);
+        market.completed_outcomes |= 1 << 2;
+        assert!(!market.is_outcome_completed(0));
+        assert!(!market.is_outcome_completed(1));
+        assert!(market.is_outcome_completed(2));
+    }
+}
@@ -1,15 +1,18 @@
 use crate::errors::*;
+use crate::state::market::MAX_MARKET_INFO_LEN;
+use crate::utils::{convert_from_float, convert_to_float, mul_div, normalize_market_info};
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 use core::fmt::Debug;
 
 #[account]
-#[derive(Debug)]
+#[derive(InitSpace, Debug)]
 pub struct Config {
     pub authority: Pubkey,
     //  use this for 2 step ownership transfer
     pub pending_authority: Pubkey,
 
-    pub backend_sign_authority: Pubkey,
+    //  market curator, creates backend markets and overrides creators
+    pub curator: Pubkey,
 
     pub team_wallet: Pubkey,
     pub team_wallet2: Pubkey,
@@ -40,6 +43,55 @@ pub struct Config {
     pub min_sol_liquidity: u64,
 
     pub initialized: bool,
+
+    //  bitflags of Config::PAUSE_*, emergency stop for the whole program
+    pub paused: u8,
+
+    //  operations guardian, can only pause the program or freeze markets
+    pub guardian: Pubkey,
+    //  manages the whitelist entries
+    pub whitelist_manager: Pubkey,
+
+    //  use these for 2 step role transfers
+    pub pending_guardian: Pubkey,
+    pub pending_curator: Pubkey,
+    pub pending_whitelist_manager: Pubkey,
+
+    //  minimum delay in seconds before a scheduled config change can be executed
+    pub timelock_delay: i64,
+    //  id of the next scheduled config change
+    pub config_change_nonce: u64,
+
+    //  whitelist tiers 1..=MAX_FEE_TIERS, tier 0 uses the small fees above
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+
+    //  merkle root of (user, tier, expiry) leaves, users activate their entry with a proof
+    pub whitelist_merkle_root: [u8; 32],
+
+    //  default per wallet limits of new markets, 0 means no limit
+    pub max_wallet_sol: u64,
+    pub max_wallet_share_bps: u64,
+
+    //  anti sniping restrictions of new markets
+    pub launch_phase: LaunchPhase,
+
+    //  a side completes once its real SOL reserves reach this amount, 0 disables graduation
+    pub graduation_threshold: u64,
+    //  migrates completed markets into pools of amm_program
+    pub migration_authority: Pubkey,
+    pub amm_program: Pubkey,
+
+    //  longest market_info in bytes accepted by the create instructions
+    pub max_market_info_len: u32,
+
+    //  SOL the global vault still owes markets created before the market vaults, scheduled
+    //  from an off-chain audit of every legacy market and consumed by the vault migrations
+    pub legacy_sol_liabilities: u64,
+
+    //  real SOL liabilities of the binary native SOL markets as last reconciled, a market
+    //  reports its change in audit_market, the vault and amm migrations and close_market,
+    //  trades do not write the config
+    pub total_sol_liabilities: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
@@ -78,3 +130,891 @@ impl<T: PartialEq + PartialOrd + Debug> AmountConfig<T> {
         }
     }
 }
+
+pub const FEE_BPS_CONFIG: AmountConfig<u64> = AmountConfig::Range {
+    min: None,
+    max: Some(10_000),
+};
+pub const LIMIT_TIMESTAMP_CONFIG: AmountConfig<i64> = AmountConfig::Range {
+    min: Some(0),
+    max: None,
+};
+//  config changes are announced at least one day before they can be executed
+pub const TIMELOCK_DELAY_CONFIG: AmountConfig<i64> = AmountConfig::Range {
+    min: Some(24 * 60 * 60),
+    max: Some(30 * 24 * 60 * 60),
+};
+pub const TOKEN_DECIMALS_CONFIG: AmountConfig<u8> = AmountConfig::Range {
+    min: None,
+    max: Some(9),
+};
+pub const CROSS_SOL_FACTOR_CONFIG: AmountConfig<f64> = AmountConfig::Range {
+    min: Some(0.0),
+    max: Some(1.0),
+};
+pub const MARKET_INFO_LEN_CONFIG: AmountConfig<u32> = AmountConfig::Range {
+    min: Some(1),
+    max: Some(MAX_MARKET_INFO_LEN as u32),
+};
+
+fn validate_field<T: PartialEq + PartialOrd + Debug>(
+    name: &str,
+    amount_config: &AmountConfig<T>,
+    value: &T,
+) -> Result<()> {
+    amount_config.validate(value).inspect_err(|_| {
+        msg!("invalid config value for {}: {:?}", name, value);
+    })
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub struct FeeParams {
+    pub platform_buy_fee: u64,
+    pub platform_sell_fee: u64,
+
+    pub platform_buy_small_fee: u64,
+    pub platform_sell_small_fee: u64,
+
+    pub creator_buy_fee: u64,
+    pub creator_sell_fee: u64,
+}
+
+pub const MAX_FEE_TIERS: usize = 3;
+
+//  platform fees of a whitelist tier
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default,
+)]
+pub struct FeeTier {
+    pub platform_buy_fee: u64,
+    pub platform_sell_fee: u64,
+    //  seconds after the first swap during which the tier applies, 0 means no limit
+    pub limit_timestamp: i64,
+}
+
+//  restrictions on buys during the first seconds of a market, 0 disables a cap
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default,
+)]
+pub struct LaunchPhase {
+    //  seconds after creation
+    pub duration: i64,
+    //  lamports per buy transaction, fees included
+    pub max_buy_sol: u64,
+    //  lamports per wallet over the whole phase, fees included
+    pub max_wallet_sol: u64,
+    //  only users with an active whitelist entry can buy
+    pub whitelist_only: bool,
+}
+
+impl LaunchPhase {
+    pub fn is_active(&self, created_at: i64, timestamp: i64) -> bool {
+        timestamp < created_at.saturating_add(self.duration)
+    }
+
+    //  buy restrictions of a market created at created_at,
+    //  wallet_sol_spent is None without a position
+    pub fn check(
+        &self,
+        created_at: i64,
+        timestamp: i64,
+        amount: u64,
+        is_whitelisted: bool,
+        wallet_sol_spent: Option<u64>,
+    ) -> Result<()> {
+        if !self.is_active(created_at, timestamp) {
+            return Ok(());
+        }
+
+        require!(
+            !self.whitelist_only || is_whitelisted,
+            TakesFunError::LaunchPhaseWhitelistOnly
+        );
+        if self.max_buy_sol > 0 {
+            require!(
+                amount <= self.max_buy_sol,
+                TakesFunError::LaunchPhaseMaxBuyExceeded
+            );
+        }
+        if self.max_wallet_sol > 0 {
+            let wallet_sol_spent = wallet_sol_spent.ok_or(MissingPositionAccount)?;
+            require!(
+                wallet_sol_spent <= self.max_wallet_sol,
+                TakesFunError::LaunchPhaseMaxWalletExceeded
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub struct MigrationParams {
+    pub graduation_threshold: u64,
+    pub migration_authority: Pubkey,
+    pub amm_program: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub struct TeamWallets {
+    pub team_wallet: Pubkey,
+    pub team_wallet2: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Debug)]
+pub struct CurveParams {
+    pub token_supply_config: u64,
+    pub token_decimals_config: u8,
+
+    pub initial_virtual_yes_token_reserves_config: u64,
+    pub initial_virtual_yes_sol_reserves_config: u64,
+    pub initial_real_yes_token_reserves_config: u64,
+
+    pub initial_virtual_no_token_reserves_config: u64,
+    pub initial_virtual_no_sol_reserves_config: u64,
+    pub initial_real_no_token_reserves_config: u64,
+
+    pub cross_sol_factor: f64,
+    pub min_sol_liquidity: u64,
+}
+
+//  partial config update, also used to report the replaced values
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Debug)]
+pub enum ConfigUpdate {
+    Fees(FeeParams),
+    TeamWallets(TeamWallets),
+    CurveParams(CurveParams),
+    WhitelistWindow {
+        limit_timestamp: i64,
+    },
+    TimelockDelay {
+        timelock_delay: i64,
+    },
+    FeeTiers {
+        fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    },
+    PositionLimits {
+        max_wallet_sol: u64,
+        max_wallet_share_bps: u64,
+    },
+    LaunchPhase(LaunchPhase),
+    Migration(MigrationParams),
+    MarketInfoLen {
+        max_market_info_len: u32,
+    },
+    LegacySolLiabilities {
+        legacy_sol_liabilities: u64,
+    },
+}
+
+//  roles below the root authority, rotated by nominate_role / accept_role
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Guardian,
+    Curator,
+    WhitelistManager,
+}
+
+impl Config {
+    pub const PAUSE_CREATE: u8 = 1 << 0;
+    pub const PAUSE_SWAP: u8 = 1 << 1;
+    pub const PAUSE_CLAIM: u8 = 1 << 2;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_CREATE | Self::PAUSE_SWAP | Self::PAUSE_CLAIM;
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    pub fn fees(&self) -> FeeParams {
+        FeeParams {
+            platform_buy_fee: self.platform_buy_fee,
+            platform_sell_fee: self.platform_sell_fee,
+            platform_buy_small_fee: self.platform_buy_small_fee,
+            platform_sell_small_fee: self.platform_sell_small_fee,
+            creator_buy_fee: self.creator_buy_fee,
+            creator_sell_fee: self.creator_sell_fee,
+        }
+    }
+
+    pub fn team_wallets(&self) -> TeamWallets {
+        TeamWallets {
+            team_wallet: self.team_wallet,
+            team_wallet2: self.team_wallet2,
+        }
+    }
+
+    pub fn migration_params(&self) -> MigrationParams {
+        MigrationParams {
+            graduation_threshold: self.graduation_threshold,
+            migration_authority: self.migration_authority,
+            amm_program: self.amm_program,
+        }
+    }
+
+    pub fn curve_params(&self) -> CurveParams {
+        CurveParams {
+            token_supply_config: self.token_supply_config,
+            token_decimals_config: self.token_decimals_config,
+            initial_virtual_yes_token_reserves_config: self
+                .initial_virtual_yes_token_reserves_config,
+            initial_virtual_yes_sol_reserves_config: self.initial_virtual_yes_sol_reserves_config,
+            initial_real_yes_token_reserves_config: self.initial_real_yes_token_reserves_config,
+            initial_virtual_no_token_reserves_config: self.initial_virtual_no_token_reserves_config,
+            initial_virtual_no_sol_reserves_config: self.initial_virtual_no_sol_reserves_config,
+            initial_real_no_token_reserves_config: self.initial_real_no_token_reserves_config,
+            cross_sol_factor: self.cross_sol_factor,
+            min_sol_liquidity: self.min_sol_liquidity,
+        }
+    }
+
+    //  platform fee for a swap, fee_tier is None for users without an active whitelist entry
+    pub fn platform_fee(&self, direction: u8, fee_tier: Option<u8>) -> u64 {
+        match (fee_tier, direction) {
+            (None, 1) => self.platform_sell_fee,
+            (None, _) => self.platform_buy_fee,
+            (Some(0), 1) => self.platform_sell_small_fee,
+            (Some(0), _) => self.platform_buy_small_fee,
+            (Some(tier), 1) => self.fee_tiers[tier as usize - 1].platform_sell_fee,
+            (Some(tier), _) => self.fee_tiers[tier as usize - 1].platform_buy_fee,
+        }
+    }
+
+    pub fn initial_virtual_token_reserves(&self, token_type: u8) -> u64 {
+        match token_type {
+            0 => self.initial_virtual_no_token_reserves_config,
+            _ => self.initial_virtual_yes_token_reserves_config,
+        }
+    }
+
+    //  virtual SOL a buy of sol_amount takes from the other curves of the market
+    pub fn cross_shift(&self, sol_amount: u64) -> u64 {
+        convert_from_float(convert_to_float(sol_amount, 9) * self.cross_sol_factor, 9)
+    }
+
+    //  a curve whose real SOL reserves reach the threshold stops trading until migrated
+    pub fn is_graduated(&self, real_sol_reserves: u64) -> bool {
+        self.graduation_threshold > 0 && real_sol_reserves >= self.graduation_threshold
+    }
+
+    //  share of the global vault holdings a legacy market moves into its market vault, the
+    //  holdings are split pro rata over legacy_sol_liabilities when they fall short of it
+    pub fn take_legacy_share(&mut self, sol_liabilities: u64, holdings: u64) -> Result<u64> {
+        require!(
+            sol_liabilities <= self.legacy_sol_liabilities,
+            TakesFunError::LegacyLiabilitiesNotAudited
+        );
+        let share = if holdings >= self.legacy_sol_liabilities {
+            sol_liabilities
+        } else {
+            mul_div(sol_liabilities, holdings, self.legacy_sol_liabilities)
+                .ok_or(ArithmeticError)?
+        };
+        self.legacy_sol_liabilities -= sol_liabilities;
+        Ok(share)
+    }
+
+    //  replaces what a market last reported in total_sol_liabilities with its current
+    //  liabilities, reported is the field of the market keeping that value
+    pub fn reconcile_sol_liabilities(
+        &mut self,
+        reported: &mut u64,
+        sol_liabilities: u64,
+    ) -> Result<()> {
+        self.total_sol_liabilities = self
+            .total_sol_liabilities
+            .checked_sub(*reported)
+            .and_then(|total| total.checked_add(sol_liabilities))
+            .ok_or(ArithmeticError)?;
+        *reported = sol_liabilities;
+        Ok(())
+    }
+
+    //  fills the fields appended after the deployed layout, they deserialize as zeroes
+    //  from the tail added by migrate_config
+    pub fn upgrade(&mut self, timelock_delay: i64) -> Result<()> {
+        if self.guardian == Pubkey::default() {
+            self.guardian = self.authority;
+        }
+        if self.whitelist_manager == Pubkey::default() {
+            self.whitelist_manager = self.curator;
+        }
+        if self.timelock_delay == 0 {
+            self.timelock_delay = timelock_delay;
+        }
+        if self.max_market_info_len == 0 {
+            self.max_market_info_len = MAX_MARKET_INFO_LEN as u32;
+        }
+        self.initialized = true;
+        self.validate()
+    }
+
+    //  market_info seeds the market pda, it has to be in its canonical form so
+    //  whitespace or unicode variants of a question can not create a second market
+    pub fn validate_market_info(&self, market_info: &str) -> Result<()> {
+        let len = market_info.len() as u32;
+        AmountConfig::Range {
+            min: Some(1),
+            max: Some(self.max_market_info_len),
+        }
+        .validate(&len)
+        .map_err(|_| {
+            msg!(
+                "market_info is {} bytes, expected 1 to {}",
+                len,
+                self.max_market_info_len
+            );
+            error!(TakesFunError::InvalidMarketInfoLength)
+        })?;
+        require!(
+            market_info == normalize_market_info(market_info),
+            TakesFunError::MarketInfoNotNormalized
+        );
+        Ok(())
+    }
+
+    pub fn role(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Guardian => self.guardian,
+            Role::Curator => self.curator,
+            Role::WhitelistManager => self.whitelist_manager,
+        }
+    }
+
+    pub fn pending_role(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Guardian => self.pending_guardian,
+            Role::Curator => self.pending_curator,
+            Role::WhitelistManager => self.pending_whitelist_manager,
+        }
+    }
+
+    pub fn set_pending_role(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::Guardian => self.pending_guardian = holder,
+            Role::Curator => self.pending_curator = holder,
+            Role::WhitelistManager => self.pending_whitelist_manager = holder,
+        }
+    }
+
+    //  promote the pending holder of a role, returns the previous holder
+    pub fn accept_role(&mut self, role: Role) -> Pubkey {
+        let new_holder = self.pending_role(role);
+        let old_holder = self.role(role);
+        match role {
+            Role::Guardian => self.guardian = new_holder,
+            Role::Curator => self.curator = new_holder,
+            Role::WhitelistManager => self.whitelist_manager = new_holder,
+        }
+        self.set_pending_role(role, Pubkey::default());
+        old_holder
+    }
+
+    //  every config write must call this before the config is persisted
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.authority != Pubkey::default(),
+            TakesFunError::IncorrectAuthority
+        );
+        require!(
+            self.guardian != Pubkey::default()
+                && self.curator != Pubkey::default()
+                && self.whitelist_manager != Pubkey::default(),
+            TakesFunError::IncorrectAuthority
+        );
+        require!(
+            self.team_wallet != Pubkey::default() && self.team_wallet2 != Pubkey::default(),
+            TakesFunError::IncorrectTeamWallet
+        );
+
+        //  fees are in bps, buy/sell fees are charged together on the same amount
+        validate_field("platform_buy_fee", &FEE_BPS_CONFIG, &self.platform_buy_fee)?;
+        validate_field(
+            "platform_sell_fee",
+            &FEE_BPS_CONFIG,
+            &self.platform_sell_fee,
+        )?;
+        validate_field(
+            "platform_buy_small_fee",
+            &FEE_BPS_CONFIG,
+            &self.platform_buy_small_fee,
+        )?;
+        validate_field(
+            "platform_sell_small_fee",
+            &FEE_BPS_CONFIG,
+            &self.platform_sell_small_fee,
+        )?;
+        validate_field("creator_buy_fee", &FEE_BPS_CONFIG, &self.creator_buy_fee)?;
+        validate_field("creator_sell_fee", &FEE_BPS_CONFIG, &self.creator_sell_fee)?;
+        let mut max_platform_buy_fee = self.platform_buy_fee.max(self.platform_buy_small_fee);
+        let mut max_platform_sell_fee = self.platform_sell_fee.max(self.platform_sell_small_fee);
+        for fee_tier in self.fee_tiers.iter() {
+            validate_field("tier_buy_fee", &FEE_BPS_CONFIG, &fee_tier.platform_buy_fee)?;
+            validate_field(
+                "tier_sell_fee",
+                &FEE_BPS_CONFIG,
+                &fee_tier.platform_sell_fee,
+            )?;
+            validate_field(
+                "tier_limit_timestamp",
+                &LIMIT_TIMESTAMP_CONFIG,
+                &fee_tier.limit_timestamp,
+            )?;
+            max_platform_buy_fee = max_platform_buy_fee.max(fee_tier.platform_buy_fee);
+            max_platform_sell_fee = max_platform_sell_fee.max(fee_tier.platform_sell_fee);
+        }
+        validate_field(
+            "total_buy_fee",
+            &FEE_BPS_CONFIG,
+            &(max_platform_buy_fee + self.creator_buy_fee),
+        )?;
+        validate_field(
+            "total_sell_fee",
+            &FEE_BPS_CONFIG,
+            &(max_platform_sell_fee + self.creator_sell_fee),
+        )?;
+
+        //  curve reserves can not be empty and real reserves come out of the minted supply
+        validate_field(
+            "token_decimals_config",
+            &TOKEN_DECIMALS_CONFIG,
+            &self.token_decimals_config,
+        )?;
+        let real_token_reserves_config = AmountConfig::Range {
+            min: Some(1),
+            max: Some(self.token_supply_config),
+        };
+        validate_field(
+            "initial_real_yes_token_reserves_config",
+            &real_token_reserves_config,
+            &self.initial_real_yes_token_reserves_config,
+        )?;
+        validate_field(
+            "initial_real_no_token_reserves_config",
+            &real_token_reserves_config,
+            &self.initial_real_no_token_reserves_config,
+        )?;
+
+        let non_zero_config = AmountConfig::Range {
+            min: Some(1),
+            max: None,
+        };
+        validate_field(
+            "initial_virtual_yes_token_reserves_config",
+            &non_zero_config,
+            &self.initial_virtual_yes_token_reserves_config,
+        )?;
+        validate_field(
+            "initial_virtual_no_token_reserves_config",
+            &non_zero_config,
+            &self.initial_virtual_no_token_reserves_config,
+        )?;
+        validate_field(
+            "initial_virtual_yes_sol_reserves_config",
+            &non_zero_config,
+            &self.initial_virtual_yes_sol_reserves_config,
+        )?;
+        validate_field(
+            "initial_virtual_no_sol_reserves_config",
+            &non_zero_config,
+            &self.initial_virtual_no_sol_reserves_config,
+        )?;
+
+        //  cross effect can never drain a side below its initial virtual liquidity
+        validate_field(
+            "min_sol_liquidity",
+            &AmountConfig::Range {
+                min: None,
+                max: Some(
+                    self.initial_virtual_yes_sol_reserves_config
+                        .min(self.initial_virtual_no_sol_reserves_config),
+                ),
+            },
+            &self.min_sol_liquidity,
+        )?;
+        require!(
+            self.cross_sol_factor.is_finite(),
+            TakesFunError::ValueInvalid
+        );
+        validate_field(
+            "cross_sol_factor",
+            &CROSS_SOL_FACTOR_CONFIG,
+            &self.cross_sol_factor,
+        )?;
+
+        validate_field(
+            "limit_timestamp",
+            &LIMIT_TIMESTAMP_CONFIG,
+            &self.limit_timestamp,
+        )?;
+
+        validate_field(
+            "timelock_delay",
+            &TIMELOCK_DELAY_CONFIG,
+            &self.timelock_delay,
+        )?;
+
+        validate_field(
+            "max_wallet_share_bps",
+            &FEE_BPS_CONFIG,
+            &self.max_wallet_share_bps,
+        )?;
+
+        validate_field(
+            "launch_phase_duration",
+            &LIMIT_TIMESTAMP_CONFIG,
+            &self.launch_phase.duration,
+        )?;
+
+        //  completed markets must be migratable
+        if self.graduation_threshold > 0 {
+            require!(
+                self.migration_authority != Pubkey::default(),
+                TakesFunError::InvalidMigrationAuthority
+            );
+            require!(
+                self.amm_program != Pubkey::default(),
+                TakesFunError::InvalidMeteoraProgram
+            );
+        }
+
+        //  the market account reserves MAX_MARKET_INFO_LEN bytes
+        validate_field(
+            "max_market_info_len",
+            &MARKET_INFO_LEN_CONFIG,
+            &self.max_market_info_len,
+        )?;
+
+        require!(
+            self.paused & !Self::PAUSE_ALL == 0,
+            TakesFunError::ValueInvalid
+        );
+
+        Ok(())
+    }
+
+    //  apply a partial update and return the values it replaced
+    pub fn apply_update(&mut self, update: ConfigUpdate) -> Result<ConfigUpdate> {
+        match update {
+            ConfigUpdate::Fees(fees) => {
+                let old = self.fees();
+                self.platform_buy_fee = fees.platform_buy_fee;
+                self.platform_sell_fee = fees.platform_sell_fee;
+                self.platform_buy_small_fee = fees.platform_buy_small_fee;
+                self.platform_sell_small_fee = fees.platform_sell_small_fee;
+                self.creator_buy_fee = fees.creator_buy_fee;
+                self.creator_sell_fee = fees.creator_sell_fee;
+                Ok(ConfigUpdate::Fees(old))
+            }
+            ConfigUpdate::TeamWallets(wallets) => {
+                let old = self.team_wallets();
+                self.team_wallet = wallets.team_wallet;
+                self.team_wallet2 = wallets.team_wallet2;
+                Ok(ConfigUpdate::TeamWallets(old))
+            }
+            ConfigUpdate::CurveParams(curve) => {
+                let old = self.curve_params();
+                self.token_supply_config = curve.token_supply_config;
+                self.token_decimals_config = curve.token_decimals_config;
+                self.initial_virtual_yes_token_reserves_config =
+                    curve.initial_virtual_yes_token_reserves_config;
+                self.initial_virtual_yes_sol_reserves_config =
+                    curve.initial_virtual_yes_sol_reserves_config;
+                self.initial_real_yes_token_reserves_config =
+                    curve.initial_real_yes_token_reserves_config;
+                self.initial_virtual_no_token_reserves_config =
+                    curve.initial_virtual_no_token_reserves_config;
+                self.initial_virtual_no_sol_reserves_config =
+                    curve.initial_virtual_no_sol_reserves_config;
+                self.initial_real_no_token_reserves_config =
+                    curve.initial_real_no_token_reserves_config;
+                self.cross_sol_factor = curve.cross_sol_factor;
+                self.min_sol_liquidity = curve.min_sol_liquidity;
+                Ok(ConfigUpdate::CurveParams(old))
+            }
+            ConfigUpdate::WhitelistWindow { limit_timestamp } => {
+                let old = self.limit_timestamp;
+                self.limit_timestamp = limit_timestamp;
+                Ok(ConfigUpdate::WhitelistWindow {
+                    limit_timestamp: old,
+                })
+            }
+            ConfigUpdate::FeeTiers { fee_tiers } => {
+                let old = self.fee_tiers;
+                self.fee_tiers = fee_tiers;
+                Ok(ConfigUpdate::FeeTiers { fee_tiers: old })
+            }
+            ConfigUpdate::TimelockDelay { timelock_delay } => {
+                let old = self.timelock_delay;
+                self.timelock_delay = timelock_delay;
+                Ok(ConfigUpdate::TimelockDelay {
+                    timelock_delay: old,
+                })
+            }
+            ConfigUpdate::PositionLimits {
+                max_wallet_sol,
+                max_wallet_share_bps,
+            } => {
+                let old = ConfigUpdate::PositionLimits {
+                    max_wallet_sol: self.max_wallet_sol,
+                    max_wallet_share_bps: self.max_wallet_share_bps,
+                };
+                self.max_wallet_sol = max_wallet_sol;
+                self.max_wallet_share_bps = max_wallet_share_bps;
+                Ok(old)
+            }
+            ConfigUpdate::LaunchPhase(launch_phase) => {
+                let old = self.launch_phase;
+                self.launch_phase = launch_phase;
+                Ok(ConfigUpdate::LaunchPhase(old))
+            }
+            ConfigUpdate::Migration(migration) => {
+                let old = self.migration_params();
+                self.graduation_threshold = migration.graduation_threshold;
+                self.migration_authority = migration.migration_authority;
+                self.amm_program = migration.amm_program;
+                Ok(ConfigUpdate::Migration(old))
+            }
+            ConfigUpdate::MarketInfoLen {
+                max_market_info_len,
+            } => {
+                let old = self.max_market_info_len;
+                self.max_market_info_len = max_market_info_len;
+                Ok(ConfigUpdate::MarketInfoLen {
+                    max_market_info_len: old,
+                })
+            }
+            ConfigUpdate::LegacySolLiabilities {
+                legacy_sol_liabilities,
+            } => {
+                let old = self.legacy_sol_liabilities;
+                self.legacy_sol_liabilities = legacy_sol_liabilities;
+                Ok(ConfigUpdate::LegacySolLiabilities {
+                    legacy_sol_liabilities: old,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::state::config_change::ConfigChange;
+    use anchor_lang::Discriminator;
+
+    //  config layout of the first release
+    #[derive(AnchorSerialize)]
+    struct LegacyConfig {
+        authority: Pubkey,
+        pending_authority: Pubkey,
+        backend_sign_authority: Pubkey,
+        team_wallet: Pubkey,
+        team_wallet2: Pubkey,
+        platform_buy_fee: u64,
+        platform_sell_fee: u64,
+        platform_buy_small_fee: u64,
+        platform_sell_small_fee: u64,
+        creator_buy_fee: u64,
+        creator_sell_fee: u64,
+        token_supply_config: u64,
+        token_decimals_config: u8,
+        initial_virtual_yes_token_reserves_config: u64,
+        initial_virtual_yes_sol_reserves_config: u64,
+        initial_real_yes_token_reserves_config: u64,
+        initial_virtual_no_token_reserves_config: u64,
+        initial_virtual_no_sol_reserves_config: u64,
+        initial_real_no_token_reserves_config: u64,
+        limit_timestamp: i64,
+        cross_sol_factor: f64,
+        min_sol_liquidity: u64,
+        initialized: bool,
+    }
+
+    fn legacy_config() -> LegacyConfig {
+        LegacyConfig {
+            authority: Pubkey::new_unique(),
+            pending_authority: Pubkey::default(),
+            backend_sign_authority: Pubkey::new_unique(),
+            team_wallet: Pubkey::new_unique(),
+            team_wallet2: Pubkey::new_unique(),
+            platform_buy_fee: 100,
+            platform_sell_fee: 100,
+            platform_buy_small_fee: 50,
+            platform_sell_small_fee: 50,
+            creator_buy_fee: 100,
+            creator_sell_fee: 100,
+            token_supply_config: 1_000_000_000_000_000,
+            token_decimals_config: 6,
+            initial_virtual_yes_token_reserves_config: 1_073_000_000_000_000,
+            initial_virtual_yes_sol_reserves_config: 30_000_000_000,
+            initial_real_yes_token_reserves_config: 793_100_000_000_000,
+            initial_virtual_no_token_reserves_config: 1_073_000_000_000_000,
+            initial_virtual_no_sol_reserves_config: 30_000_000_000,
+            initial_real_no_token_reserves_config: 793_100_000_000_000,
+            limit_timestamp: 0,
+            cross_sol_factor: 0.5,
+            min_sol_liquidity: 1_000_000_000,
+            initialized: true,
+        }
+    }
+
+    fn migrate(legacy: &LegacyConfig, timelock_delay: i64) -> Result<Config> {
+        let mut data = Config::DISCRIMINATOR.to_vec();
+        data.extend(legacy.try_to_vec().unwrap());
+        assert!(data.len() < 8 + Config::INIT_SPACE);
+        data.resize(8 + Config::INIT_SPACE, 0);
+
+        let mut config = Config::try_deserialize(&mut data.as_slice())?;
+        config.upgrade(timelock_delay)?;
+        Ok(config)
+    }
+
+    //  valid config for the tests of other modules
+    pub(crate) fn test_config() -> Config {
+        migrate(&legacy_config(), 24 * 60 * 60).unwrap()
+    }
+
+    #[test]
+    fn legacy_config_upgrades_with_defaults() {
+        let legacy = legacy_config();
+        let config = migrate(&legacy, 24 * 60 * 60).unwrap();
+
+        assert_eq!(config.authority, legacy.authority);
+        assert_eq!(config.curator, legacy.backend_sign_authority);
+        assert_eq!(config.guardian, legacy.authority);
+        assert_eq!(config.whitelist_manager, legacy.backend_sign_authority);
+        assert_eq!(config.timelock_delay, 24 * 60 * 60);
+        assert_eq!(config.max_market_info_len, MAX_MARKET_INFO_LEN as u32);
+        assert_eq!(config.platform_buy_fee, legacy.platform_buy_fee);
+        assert_eq!(config.min_sol_liquidity, legacy.min_sol_liquidity);
+        assert_eq!(config.paused, 0);
+        assert_eq!(config.graduation_threshold, 0);
+
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + Config::INIT_SPACE);
+    }
+
+    #[test]
+    fn legacy_config_upgrade_validates_timelock() {
+        assert!(migrate(&legacy_config(), 365 * 24 * 60 * 60).is_err());
+    }
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    #[test]
+    fn timelock_delay_is_at_least_one_day() {
+        assert!(migrate(&legacy_config(), 0).is_err());
+        assert!(migrate(&legacy_config(), DAY - 1).is_err());
+        assert!(migrate(&legacy_config(), DAY).is_ok());
+        assert!(migrate(&legacy_config(), 30 * DAY).is_ok());
+        assert!(migrate(&legacy_config(), 30 * DAY + 1).is_err());
+
+        let mut config = migrate(&legacy_config(), DAY).unwrap();
+        config
+            .apply_update(ConfigUpdate::TimelockDelay { timelock_delay: 0 })
+            .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn config_change_respects_timelock() {
+        let config = migrate(&legacy_config(), 2 * DAY).unwrap();
+        let now = 1_700_000_000;
+
+        assert!(ConfigChange::check_execute_after(&config, now, now).is_err());
+        assert!(ConfigChange::check_execute_after(&config, now + 2 * DAY - 1, now).is_err());
+        assert!(ConfigChange::check_execute_after(&config, now + 2 * DAY, now).is_ok());
+        assert!(ConfigChange::check_execute_after(&config, i64::MAX, i64::MAX).is_ok());
+
+        let change = ConfigChange {
+            id: 0,
+            authority: config.authority,
+            execute_after: now + 2 * DAY,
+            change: ConfigUpdate::TimelockDelay {
+                timelock_delay: DAY,
+            },
+        };
+        assert!(change.check_executable(now).is_err());
+        assert!(change.check_executable(now + 2 * DAY - 1).is_err());
+        assert!(change.check_executable(now + 2 * DAY).is_ok());
+    }
+
+    #[test]
+    fn legacy_markets_share_a_short_global_vault_pro_rata() {
+        let mut config = test_config();
+        config.legacy_sol_liabilities = 1_000;
+
+        //  the vault holds 600 of the 1_000 owed, every market gets 60%
+        assert_eq!(config.take_legacy_share(500, 600).unwrap(), 300);
+        assert_eq!(config.legacy_sol_liabilities, 500);
+        assert_eq!(config.take_legacy_share(300, 300).unwrap(), 180);
+        assert_eq!(config.take_legacy_share(200, 120).unwrap(), 120);
+        assert_eq!(config.legacy_sol_liabilities, 0);
+
+        //  markets the audited total does not cover can not migrate
+        assert_eq!(
+            config.take_legacy_share(1, 1_000).unwrap_err(),
+            error!(TakesFunError::LegacyLiabilitiesNotAudited)
+        );
+    }
+
+    #[test]
+    fn legacy_markets_are_paid_in_full_by_a_solvent_vault() {
+        let mut config = test_config();
+        config.legacy_sol_liabilities = 1_000;
+        assert_eq!(config.take_legacy_share(400, 5_000).unwrap(), 400);
+        assert_eq!(config.take_legacy_share(600, 4_600).unwrap(), 600);
+    }
+
+    #[test]
+    fn market_info_is_bounded_and_normalized() {
+        let mut config = test_config();
+        config.max_market_info_len = 16;
+
+        assert!(config.validate_market_info("Will it rain?").is_ok());
+        assert_eq!(
+            config.validate_market_info("").unwrap_err(),
+            error!(TakesFunError::InvalidMarketInfoLength)
+        );
+        assert_eq!(
+            config
+                .validate_market_info("Will it rain tomorrow?")
+                .unwrap_err(),
+            error!(TakesFunError::InvalidMarketInfoLength)
+        );
+
+        //  padded and decomposed variants of a question are rejected
+        assert_eq!(
+            config.validate_market_info(" Will it rain?").unwrap_err(),
+            error!(TakesFunError::MarketInfoNotNormalized)
+        );
+        assert_eq!(
+            config.validate_market_info("Cafe\u{301}?").unwrap_err(),
+            error!(TakesFunError::MarketInfoNotNormalized)
+        );
+        assert!(config.validate_market_info("Caf\u{e9}?").is_ok());
+    }
+
+    #[test]
+    fn markets_reconcile_their_liabilities_into_the_total() {
+        let mut config = test_config();
+        let (mut first, mut second) = (0, 0);
+
+        config.reconcile_sol_liabilities(&mut first, 700).unwrap();
+        config.reconcile_sol_liabilities(&mut second, 300).unwrap();
+        assert_eq!(config.total_sol_liabilities, 1_000);
+
+        //  only the change since the last report moves the total
+        config.reconcile_sol_liabilities(&mut first, 500).unwrap();
+        assert_eq!(config.total_sol_liabilities, 800);
+        config.reconcile_sol_liabilities(&mut second, 0).unwrap();
+        assert_eq!((config.total_sol_liabilities, second), (500, 0));
+
+        //  a report the total does not cover is an error, not a silent clamp
+        let mut stale = 600;
+        assert_eq!(
+            config.reconcile_sol_liabilities(&mut stale, 0).unwrap_err(),
+            error!(TakesFunError::ArithmeticError)
+        );
+    }
+}
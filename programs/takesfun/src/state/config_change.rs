@@ -0,0 +1,30 @@
+use crate::errors::TakesFunError;
+use crate::state::config::{Config, ConfigUpdate};
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+
+//  config change queued by the authority, executable by anyone once the timelock has passed
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ConfigChange {
+    pub id: u64,
+    //  paid the rent, refunded when the change is executed or cancelled
+    pub authority: Pubkey,
+    pub execute_after: i64,
+    pub change: ConfigUpdate,
+}
+
+impl ConfigChange {
+    //  a change can not be scheduled to run before the timelock of the current config
+    pub fn check_execute_after(config: &Config, execute_after: i64, now: i64) -> Result<()> {
+        require!(
+            execute_after >= now.saturating_add(config.timelock_delay),
+            TakesFunError::ValueTooSmall
+        );
+        Ok(())
+    }
+
+    pub fn check_executable(&self, now: i64) -> Result<()> {
+        require!(now >= self.execute_after, TakesFunError::TimelockNotElapsed);
+        Ok(())
+    }
+}
@@ -0,0 +1,445 @@
+use crate::errors::*;
+use crate::state::config::*;
+use crate::utils::*;
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use anchor_spl::token_interface::TokenInterface;
+
+//  constant product curve of one side of a binary market or one outcome of a categorical
+//  market, tokens have 6 decimals and are scaled to the 9 decimals of SOL for the math
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Default, Debug)]
+pub struct Curve {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SellResult {
+    pub token_amount: u64,
+    pub sol_amount: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BuyResult {
+    pub token_amount: u64,
+    pub sol_amount: u64,
+}
+
+//  the traded tokens, they move between the atas of the global vault and of the user.
+//  soulbound tokens can not move, they are minted to and burned from the user ata
+pub struct SwapSide<'info> {
+    pub mint: AccountInfo<'info>,
+    pub decimals: u8,
+    pub mint_supply: u64,
+    pub global_ata: AccountInfo<'info>,
+    pub user_ata: AccountInfo<'info>,
+    pub soulbound: bool,
+}
+
+impl<'info> SwapSide<'info> {
+    //  the mint is read from its data, so a mint created in the same instruction can be traded
+    pub fn new(
+        mint: AccountInfo<'info>,
+        global_ata: AccountInfo<'info>,
+        user_ata: AccountInfo<'info>,
+    ) -> Result<Self> {
+        let (decimals, mint_supply) = mint_state(&mint)?;
+        Ok(Self {
+            soulbound: is_soulbound(&mint)?,
+            mint,
+            decimals,
+            mint_supply,
+            global_ata,
+            user_ata,
+        })
+    }
+
+    //  supply the position share limit is measured against, soulbound mints only hold the
+    //  circulating tokens so the configured supply stands in for them
+    pub fn supply(&self, global_config: &Config) -> u64 {
+        if self.soulbound {
+            global_config.token_supply_config
+        } else {
+            self.mint_supply
+        }
+    }
+}
+
+//  sol_amount is the curve side of the swap, fees are paid on top of buys and out of sells
+#[derive(Debug, Clone)]
+pub struct SwapResult {
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub platform_fee_lamports: u64,
+    pub creator_fee_lamports: u64,
+}
+
+impl SwapResult {
+    pub fn fee_lamports(&self) -> u64 {
+        self.platform_fee_lamports + self.creator_fee_lamports
+    }
+}
+
+impl Curve {
+    pub fn get_tokens_for_buy_sol(&self, sol_amount: u64) -> Option<u64> {
+        if sol_amount == 0 {
+            return None;
+        }
+
+        let current_sol = self.virtual_sol_reserves as u128;
+        // Convert to a common decimal basis (using 9 decimals as base)
+        let current_tokens = (self.virtual_token_reserves as u128)
+            .checked_mul(1_000_000_000)?
+            .checked_div(1_000_000)?;
+
+        // Calculate new reserves using constant product formula
+        let new_sol = current_sol.checked_add(sol_amount as u128)?;
+        let new_tokens = (current_sol.checked_mul(current_tokens)?).checked_div(new_sol)?;
+
+        // Convert back to 6 decimal places for tokens
+        let tokens_out = current_tokens
+            .checked_sub(new_tokens)?
+            .checked_mul(1_000_000)?
+            .checked_div(1_000_000_000)?;
+
+        tokens_out.try_into().ok()
+    }
+
+    pub fn get_sol_for_sell_tokens(&self, token_amount: u64) -> Option<u64> {
+        if token_amount == 0 {
+            return None;
+        }
+
+        let current_sol = self.virtual_sol_reserves as u128;
+        // Convert to a common decimal basis (using 9 decimals as base)
+        let current_tokens = (self.virtual_token_reserves as u128)
+            .checked_mul(1_000_000_000)?
+            .checked_div(1_000_000)?;
+
+        // Calculate new reserves using constant product formula
+        let new_tokens = current_tokens.checked_add(
+            (token_amount as u128)
+                .checked_mul(1_000_000_000)?
+                .checked_div(1_000_000)?,
+        )?;
+        let new_sol = (current_sol.checked_mul(current_tokens)?).checked_div(new_tokens)?;
+
+        current_sol.checked_sub(new_sol)?.try_into().ok()
+    }
+
+    pub fn apply_buy(&mut self, sol_amount: u64) -> Option<BuyResult> {
+        let token_amount = self.get_tokens_for_buy_sol(sol_amount)?;
+        if token_amount >= self.real_token_reserves {
+            msg!(
+                "Error: BuyTokenAmountInvalid - token_amount {} exceeds real_reserves {}",
+                token_amount,
+                self.real_token_reserves
+            );
+            return None;
+        }
+
+        self.virtual_token_reserves = self.virtual_token_reserves.checked_sub(token_amount)?;
+        self.real_token_reserves = self.real_token_reserves.checked_sub(token_amount)?;
+        self.virtual_sol_reserves = self.virtual_sol_reserves.checked_add(sol_amount)?;
+        self.real_sol_reserves = self.real_sol_reserves.checked_add(sol_amount)?;
+
+        Some(BuyResult {
+            token_amount,
+            sol_amount,
+        })
+    }
+
+    pub fn apply_sell(&mut self, token_amount: u64) -> Option<SellResult> {
+        let sol_amount = self.get_sol_for_sell_tokens(token_amount)?;
+
+        self.virtual_token_reserves = self.virtual_token_reserves.checked_add(token_amount)?;
+        self.real_token_reserves = self.real_token_reserves.checked_add(token_amount)?;
+        self.virtual_sol_reserves = self.virtual_sol_reserves.checked_sub(sol_amount)?;
+        self.real_sol_reserves = self.real_sol_reserves.checked_sub(sol_amount)?;
+
+        Some(SellResult {
+            token_amount,
+            sol_amount,
+        })
+    }
+
+    //  real SOL backing the tokens sold by the curve at its current virtual reserves
+    pub fn expected_real_sol_reserves(&self, initial_virtual_token_reserves: u64) -> Option<u64> {
+        let current_sol = self.virtual_sol_reserves as u128;
+        let basic_virtual_sol_reserves = (current_sol
+            .checked_mul(self.virtual_token_reserves as u128)?)
+        .checked_div(initial_virtual_token_reserves as u128)?;
+
+        current_sol
+            .checked_sub(basic_virtual_sol_reserves)?
+            .try_into()
+            .ok()
+    }
+
+    //  top up the real reserves to what the virtual curve already sold
+    pub fn check_update_real_sol_reserves(
+        &mut self,
+        initial_virtual_token_reserves: u64,
+    ) -> Option<u64> {
+        let expected_sol_reserves =
+            self.expected_real_sol_reserves(initial_virtual_token_reserves)?;
+        if expected_sol_reserves > self.real_sol_reserves {
+            msg!("expected_sol_reserves > current_sol_reserves");
+            self.real_sol_reserves = expected_sol_reserves;
+        }
+        Some(expected_sol_reserves)
+    }
+
+    //  cross effect of a buy on another curve, takes up to shift_sol of virtual SOL and releases
+    //  the real SOL the curve no longer needs, both keep min_sol_liquidity,
+    //  returns the (virtual, real) shifts
+    pub fn remove_liquidity(
+        &mut self,
+        shift_sol: u64,
+        initial_virtual_token_reserves: u64,
+        min_sol_liquidity: u64,
+    ) -> Option<(u64, u64)> {
+        let max_can_remove_virtual = self.virtual_sol_reserves.saturating_sub(min_sol_liquidity);
+        let virtual_shift = shift_sol.min(max_can_remove_virtual);
+        self.virtual_sol_reserves -= virtual_shift;
+
+        let expected_real_sol_reserves =
+            self.expected_real_sol_reserves(initial_virtual_token_reserves)?;
+        let max_can_remove_real = self.real_sol_reserves.saturating_sub(min_sol_liquidity);
+        let real_shift = self
+            .real_sol_reserves
+            .saturating_sub(expected_real_sol_reserves)
+            .min(max_can_remove_real);
+        self.real_sol_reserves -= real_shift;
+
+        Some((virtual_shift, real_shift))
+    }
+
+    //  buy or sell on the curve, moves the tokens, the collateral and the fees,
+    //  shared by the swaps of binary and categorical markets
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap<'info>(
+        &mut self,
+        global_config: &Config,
+        side: SwapSide<'info>,
+
+        source: &AccountInfo<'info>,
+        team_wallet: &AccountInfo<'info>,
+        creator_vault: &AccountInfo<'info>,
+
+        amount: u64,
+        direction: u8,
+        minimum_receive_amount: u64,
+        fee_tier: Option<u8>,
+
+        user: &Signer<'info>,
+        signer: &[&[&[u8]]],
+
+        token_program: &Interface<'info, TokenInterface>,
+        collateral: &Collateral<'_, 'info>,
+    ) -> Result<SwapResult> {
+        if direction == 1 {
+            //Sell tokens
+            let sell_result = self.apply_sell(amount).ok_or(TakesFunError::SellFailed)?;
+            msg!("SellResult: {:#?}", sell_result);
+
+            let sol_amount = sell_result.sol_amount;
+            let platform_fee_lamports = bps_mul(
+                global_config.platform_fee(direction, fee_tier),
+                sol_amount,
+                10_000,
+            )
+            .ok_or(TakesFunError::ArithmeticError)?;
+            msg!(
+                "Platform Fee: {} SOL, tier: {:?}",
+                platform_fee_lamports,
+                fee_tier
+            );
+            let creator_fee_lamports = bps_mul(global_config.creator_sell_fee, sol_amount, 10_000)
+                .ok_or(TakesFunError::ArithmeticError)?;
+            msg!("Creator Fee: {} SOL", creator_fee_lamports);
+
+            //complete sell
+            let sell_amount_minus_fee = sol_amount
+                .checked_sub(platform_fee_lamports + creator_fee_lamports)
+                .ok_or(TakesFunError::ArithmeticError)?;
+            require!(
+                sell_amount_minus_fee >= minimum_receive_amount,
+                TakesFunError::SlippageExceeded
+            );
+
+            // Transfer tokens to market
+            if side.soulbound {
+                token_burn_user(
+                    side.user_ata,
+                    user,
+                    side.mint,
+                    token_program,
+                    sell_result.token_amount,
+                )?;
+            } else {
+                token_transfer_user(
+                    side.user_ata,
+                    user,
+                    side.global_ata,
+                    side.mint,
+                    side.decimals,
+                    token_program,
+                    sell_result.token_amount,
+                )?;
+            }
+            msg!("Token to market transfer complete");
+
+            // Transfer SOL to user
+            collateral.transfer_to_user(user, sell_amount_minus_fee)?;
+            msg!("SOL to user transfer complete");
+
+            if platform_fee_lamports > 0 {
+                collateral.transfer_from_vault(team_wallet.clone(), platform_fee_lamports)?;
+                msg!("Fee to team_wallet transfer complete");
+            }
+
+            if creator_fee_lamports > 0 {
+                collateral.transfer_from_vault(creator_vault.clone(), creator_fee_lamports)?;
+                msg!("Fee to creator_wallet transfer complete");
+            }
+
+            Ok(SwapResult {
+                sol_amount,
+                token_amount: sell_result.token_amount,
+                platform_fee_lamports,
+                creator_fee_lamports,
+            })
+        } else {
+            let platform_fee_lamports = bps_mul(
+                global_config.platform_fee(direction, fee_tier),
+                amount,
+                10_000,
+            )
+            .ok_or(TakesFunError::ArithmeticError)?;
+            msg!(
+                "Platform Fee: {} SOL, tier: {:?}",
+                platform_fee_lamports,
+                fee_tier
+            );
+            let creator_fee_lamports = bps_mul(global_config.creator_buy_fee, amount, 10_000)
+                .ok_or(TakesFunError::ArithmeticError)?;
+            msg!("Creator Fee: {} SOL", creator_fee_lamports);
+
+            let buy_amount_applied = amount
+                .checked_sub(platform_fee_lamports + creator_fee_lamports)
+                .ok_or(TakesFunError::ArithmeticError)?;
+
+            // Buy tokens
+            let buy_result = self
+                .apply_buy(buy_amount_applied)
+                .ok_or(TakesFunError::BuyFailed)?;
+            msg!("BuyResult: {:#?}", buy_result);
+
+            //complete buy
+            require!(
+                buy_result.token_amount >= minimum_receive_amount,
+                TakesFunError::SlippageExceeded
+            );
+
+            // Transfer tokens to user
+            if side.soulbound {
+                token_mint_with_signer(
+                    side.user_ata,
+                    source.clone(),
+                    side.mint,
+                    token_program,
+                    signer,
+                    buy_result.token_amount,
+                )?;
+            } else {
+                token_transfer_with_signer(
+                    side.global_ata,
+                    source.clone(),
+                    side.user_ata,
+                    side.mint,
+                    side.decimals,
+                    token_program,
+                    signer,
+                    buy_result.token_amount,
+                )?;
+            }
+            msg!("Token transfer complete");
+
+            //Transfer sol to market
+            collateral.transfer_to_vault(user, buy_result.sol_amount)?;
+            msg!("SOL to bonding curve transfer complete");
+
+            if platform_fee_lamports > 0 {
+                collateral.transfer_from_user(user, team_wallet.clone(), platform_fee_lamports)?;
+                msg!("Fee to team_wallet transfer complete");
+            }
+
+            if creator_fee_lamports > 0 {
+                collateral.transfer_from_user(user, creator_vault.clone(), creator_fee_lamports)?;
+                msg!("Fee to creator_wallet transfer complete");
+            }
+
+            Ok(SwapResult {
+                sol_amount: buy_result.sol_amount,
+                token_amount: buy_result.token_amount,
+                platform_fee_lamports,
+                creator_fee_lamports,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn test_curve() -> Curve {
+        Curve {
+            virtual_sol_reserves: 20 * SOL,
+            virtual_token_reserves: 1_000_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 800_000_000_000_000,
+        }
+    }
+
+    #[test]
+    fn buy_then_sell_returns_at_most_the_sol_paid() {
+        let mut curve = test_curve();
+        let buy = curve.apply_buy(SOL).unwrap();
+        assert!(buy.token_amount > 0);
+        assert_eq!(curve.real_sol_reserves, SOL);
+
+        let sell = curve.apply_sell(buy.token_amount).unwrap();
+        assert!(sell.sol_amount <= SOL);
+        assert_eq!(curve.real_sol_reserves, SOL - sell.sol_amount);
+        assert_eq!(curve.real_token_reserves, test_curve().real_token_reserves);
+    }
+
+    #[test]
+    fn buy_can_not_take_the_real_token_reserves() {
+        let mut curve = test_curve();
+        curve.real_token_reserves = 1;
+        assert!(curve.apply_buy(SOL).is_none());
+        assert_eq!(curve.real_sol_reserves, 0);
+    }
+
+    #[test]
+    fn remove_liquidity_keeps_min_sol_liquidity() {
+        let mut curve = test_curve();
+        let initial_virtual_token_reserves = curve.virtual_token_reserves;
+        curve.apply_buy(5 * SOL).unwrap();
+
+        let (virtual_shift, real_shift) = curve
+            .remove_liquidity(100 * SOL, initial_virtual_token_reserves, SOL)
+            .unwrap();
+        assert_eq!(virtual_shift, 24 * SOL);
+        assert_eq!(curve.virtual_sol_reserves, SOL);
+        assert_eq!(real_shift, 4 * SOL);
+        assert_eq!(curve.real_sol_reserves, SOL);
+    }
+}
@@ -1,12 +1,17 @@
+use crate::constants::RESOLUTION_CHALLENGE_PERIOD;
 use crate::errors::*;
 use crate::events::*;
 use crate::state::config::*;
+use crate::state::curve::*;
+use crate::state::position::{Position, PositionLimits};
 use crate::utils::*;
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
-use anchor_spl::token::Mint;
-use anchor_spl::token::Token;
+use anchor_spl::token_interface::TokenInterface;
+
+pub const MAX_MARKET_INFO_LEN: usize = 200;
 
 #[account]
+#[derive(InitSpace)]
 pub struct Market {
     pub yes_token_mint: Pubkey,
     pub no_token_mint: Pubkey,
@@ -26,31 +31,390 @@ pub struct Market {
     pub real_no_sol_reserves: u64,
 
     pub is_completed: bool,
+    #[max_len(MAX_MARKET_INFO_LEN)]
     pub market_info: String,
+
+    //  use this for 2 step creator handover
+    pub pending_creator: Pubkey,
+
+    //  frozen markets can not be traded or claimed
+    pub frozen: bool,
+
+    //  per wallet limits, copied from the config on creation, 0 means no limit
+    pub max_wallet_sol: u64,
+    pub max_wallet_share_bps: u64,
+
+    pub created_at: i64,
+    //  copied from the config on creation
+    pub launch_phase: LaunchPhase,
+
+    //  set once the real SOL reserves of a side reach Config::graduation_threshold,
+    //  trading of that side stops until the market is migrated
+    pub yes_completed: bool,
+    pub no_completed: bool,
+
+    //  scalar markets settle linearly between the bounds, YES is LONG and NO is SHORT,
+    //  binary markets are scalar markets with bounds 0..1
+    pub is_scalar: bool,
+    pub lower_bound: i64,
+    pub upper_bound: i64,
+
+    //  set by resolve_market, payout pools and outstanding tokens are snapshotted
+    pub resolved: bool,
+    pub resolved_value: i64,
+    pub yes_payout_pool: u64,
+    pub no_payout_pool: u64,
+    pub yes_outstanding: u64,
+    pub no_outstanding: u64,
+    pub yes_redeemed: u64,
+    pub no_redeemed: u64,
+
+    //  default for native SOL, otherwise an spl mint held in the global vault ata,
+    //  reserves, fees and limits of the market are in its base units
+    pub collateral_mint: Pubkey,
+
+    //  SOL reserves sit in the market vault pda, markets created before it keep them in the
+    //  global vault until migrate_market_vault
+    pub has_market_vault: bool,
+
+    //  paid the market rent, refunded by close_market
+    pub payer: Pubkey,
+    pub resolved_at: i64,
+
+    //  layout version, accounts below Market::VERSION are upgraded by migrate_market
+    pub version: u8,
+    //  0 until the first swap, the market metadata is locked afterwards
+    pub first_trade_at: i64,
+
+    //  resolution proposed by resolve_market, finalize_resolution applies it once
+    //  RESOLUTION_CHALLENGE_PERIOD passed without a challenge, proposed_at is 0 without one
+    pub proposed_at: i64,
+    pub proposed_value: i64,
+    pub proposer: Pubkey,
+
+    //  sol_liabilities last reported in Config::total_sol_liabilities, takes the 8 bytes
+    //  reserved by version 2 so it reads as 0 until the first report
+    pub reported_sol_liabilities: u64,
 }
 
-#[derive(Debug, Clone)]
-pub struct SellResult {
-    pub token_amount: u64,
-    pub sol_amount: u64,
+//  bounds of a scalar market, see create_market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ScalarBounds {
+    pub lower_bound: i64,
+    pub upper_bound: i64,
 }
 
-#[derive(Debug, Clone)]
-pub struct BuyResult {
-    pub token_amount: u64,
-    pub sol_amount: u64,
+impl Market {
+    pub const VERSION: u8 = 2;
+
+    pub fn is_sol_collateral(&self) -> bool {
+        self.collateral_mint == Pubkey::default()
+    }
+
+    pub fn set_bounds(&mut self, scalar_bounds: Option<ScalarBounds>) -> Result<()> {
+        match scalar_bounds {
+            Some(bounds) => {
+                require!(
+                    bounds.upper_bound > bounds.lower_bound,
+                    TakesFunError::ValueInvalid
+                );
+                self.is_scalar = true;
+                self.lower_bound = bounds.lower_bound;
+                self.upper_bound = bounds.upper_bound;
+            }
+            None => {
+                self.is_scalar = false;
+                self.lower_bound = 0;
+                self.upper_bound = 1;
+            }
+        }
+        Ok(())
+    }
+
+    //  fills the version 2 fields of a version 0 or 1 market, the account was zero extended
+    //  by migrate_market so they deserialize as zero, fields whose zero is a valid value
+    //  are left as they are
+    pub fn upgrade(&mut self) -> Result<()> {
+        require!(
+            self.version < Self::VERSION,
+            TakesFunError::MarketAlreadyUpgraded
+        );
+
+        //  the creation payer was not recorded before, refund close_market rent to the creator
+        if self.payer == Pubkey::default() {
+            self.payer = self.creator;
+        }
+        //  trades before version 2 were not recorded, keep the metadata of older markets locked
+        if self.first_trade_at == 0 {
+            self.first_trade_at = self.created_at.max(1);
+        }
+        self.version = Self::VERSION;
+        Ok(())
+    }
+
+    pub fn bounds(&self) -> (i64, i64) {
+        if self.is_scalar {
+            (self.lower_bound, self.upper_bound)
+        } else {
+            (0, 1)
+        }
+    }
+
+    //  binary markets only accept 0 (NO) or 1 (YES), scalar values are clamped to the bounds
+    fn check_resolution_value(&self, value: i64) -> Result<i64> {
+        if !self.is_scalar {
+            require!(value == 0 || value == 1, TakesFunError::ValueInvalid);
+        }
+        let (lower_bound, upper_bound) = self.bounds();
+        Ok(value.clamp(lower_bound, upper_bound))
+    }
+
+    pub fn has_resolution_proposal(&self) -> bool {
+        self.proposed_at != 0
+    }
+
+    //  first step of the resolution, trading stops until the proposal is challenged or applied
+    pub fn propose_resolution(
+        &mut self,
+        value: i64,
+        proposer: Pubkey,
+        timestamp: i64,
+    ) -> Result<()> {
+        require!(!self.resolved, TakesFunError::MarketResolved);
+        require!(!self.is_completed, TakesFunError::MarketAlreadyCompleted);
+        //  a graduated side belongs to the amm, its sol can not be paid out to winners
+        require!(
+            !self.yes_completed && !self.no_completed,
+            TakesFunError::MarketGraduated
+        );
+        require!(
+            !self.has_resolution_proposal(),
+            TakesFunError::ResolutionAlreadyProposed
+        );
+
+        self.proposed_value = self.check_resolution_value(value)?;
+        self.proposed_at = timestamp.max(1);
+        self.proposer = proposer;
+        Ok(())
+    }
+
+    //  a challenged proposal is dropped, the market trades again until the next one
+    pub fn challenge_resolution(&mut self) -> Result<()> {
+        require!(
+            self.has_resolution_proposal(),
+            TakesFunError::ResolutionNotProposed
+        );
+        self.proposed_at = 0;
+        self.proposed_value = 0;
+        self.proposer = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn challenge_deadline(&self) -> i64 {
+        self.proposed_at.saturating_add(RESOLUTION_CHALLENGE_PERIOD)
+    }
+
+    //  applies the proposal once its challenge period passed
+    pub fn finalize_resolution(
+        &mut self,
+        timestamp: i64,
+        yes_outstanding: u64,
+        no_outstanding: u64,
+        available: u64,
+    ) -> Result<()> {
+        require!(
+            self.has_resolution_proposal(),
+            TakesFunError::ResolutionNotProposed
+        );
+        require!(
+            timestamp >= self.challenge_deadline(),
+            TakesFunError::ChallengePeriodNotElapsed
+        );
+
+        self.resolve(
+            self.proposed_value,
+            yes_outstanding,
+            no_outstanding,
+            available,
+        )?;
+        self.resolved_at = timestamp;
+        self.proposed_at = 0;
+        Ok(())
+    }
+
+    //  settle the market on value, the payout pools split the reserves linearly between the
+    //  bounds and never exceed available, the collateral the vault actually holds
+    pub fn resolve(
+        &mut self,
+        value: i64,
+        yes_outstanding: u64,
+        no_outstanding: u64,
+        available: u64,
+    ) -> Result<()> {
+        let value = self.check_resolution_value(value)?;
+        let (lower_bound, upper_bound) = self.bounds();
+
+        let total_pool = self
+            .real_yes_sol_reserves
+            .checked_add(self.real_no_sol_reserves)
+            .ok_or(TakesFunError::OverflowOrUnderflowOccurred)?
+            .min(available);
+        let mut yes_payout_pool: u64 = ((total_pool as u128)
+            * ((value as i128 - lower_bound as i128) as u128)
+            / ((upper_bound as i128 - lower_bound as i128) as u128))
+            .try_into()
+            .map_err(|_| TakesFunError::OverflowOrUnderflowOccurred)?;
+        let mut no_payout_pool = total_pool - yes_payout_pool;
+
+        //  a side nobody holds can not be redeemed, its share goes to the other side
+        if yes_outstanding == 0 {
+            no_payout_pool += yes_payout_pool;
+            yes_payout_pool = 0;
+        }
+        if no_outstanding == 0 {
+            yes_payout_pool += no_payout_pool;
+            no_payout_pool = 0;
+        }
+
+        self.resolved = true;
+        self.resolved_value = value;
+        self.yes_payout_pool = yes_payout_pool;
+        self.no_payout_pool = no_payout_pool;
+        self.yes_outstanding = yes_outstanding;
+        self.no_outstanding = no_outstanding;
+        Ok(())
+    }
+
+    //  SOL paid for redeeming token_amount tokens, pro rata on the snapshot
+    pub fn redemption_amount(&self, token_type: u8, token_amount: u64) -> Option<u64> {
+        let (payout_pool, outstanding) = match token_type {
+            0 => (self.no_payout_pool, self.no_outstanding),
+            _ => (self.yes_payout_pool, self.yes_outstanding),
+        };
+        ((payout_pool as u128)
+            .checked_mul(token_amount as u128)?
+            .checked_div(outstanding as u128)?)
+        .try_into()
+        .ok()
+    }
+
+    //  SOL still owed to traders, the reserves or the unredeemed part of the payout pools
+    pub fn sol_liabilities(&self) -> Option<u64> {
+        if !self.resolved {
+            return self
+                .real_yes_sol_reserves
+                .checked_add(self.real_no_sol_reserves);
+        }
+        let yes =
+            self.redemption_amount(1, self.yes_outstanding.checked_sub(self.yes_redeemed)?)?;
+        let no = self.redemption_amount(0, self.no_outstanding.checked_sub(self.no_redeemed)?)?;
+        yes.checked_add(no)
+    }
+
+    //  scales what the market owes down to the SOL it received from the global vault when
+    //  the vault migration could only pay part of sol_liabilities
+    pub fn write_down_sol_liabilities(&mut self, paid: u64, owed: u64) -> Result<()> {
+        if paid >= owed {
+            return Ok(());
+        }
+        let scale = |value: u64| mul_div(value, paid, owed).ok_or(TakesFunError::ArithmeticError);
+        self.real_yes_sol_reserves = scale(self.real_yes_sol_reserves)?;
+        self.real_no_sol_reserves = scale(self.real_no_sol_reserves)?;
+        self.yes_payout_pool = scale(self.yes_payout_pool)?;
+        self.no_payout_pool = scale(self.no_payout_pool)?;
+        Ok(())
+    }
+
+    pub fn position_limits(&self) -> PositionLimits {
+        PositionLimits {
+            max_wallet_sol: self.max_wallet_sol,
+            max_wallet_share_bps: self.max_wallet_share_bps,
+        }
+    }
+
+    pub fn is_side_completed(&self, token_type: u8) -> bool {
+        match token_type {
+            0 => self.no_completed,
+            _ => self.yes_completed,
+        }
+    }
+
+    pub fn is_launch_phase(&self, timestamp: i64) -> bool {
+        self.launch_phase.is_active(self.created_at, timestamp)
+    }
+
+    //  buy restrictions of the launch phase, wallet_sol_spent is None without a position
+    pub fn check_launch_phase(
+        &self,
+        timestamp: i64,
+        amount: u64,
+        is_whitelisted: bool,
+        wallet_sol_spent: Option<u64>,
+    ) -> Result<()> {
+        self.launch_phase.check(
+            self.created_at,
+            timestamp,
+            amount,
+            is_whitelisted,
+            wallet_sol_spent,
+        )
+    }
+
+    //  curve of a side, the reserves are stored field by field in the market layout
+    pub fn curve(&self, token_type: u8) -> Curve {
+        match token_type {
+            0 => Curve {
+                virtual_sol_reserves: self.virtual_no_sol_reserves,
+                virtual_token_reserves: self.virtual_no_token_reserves,
+                real_sol_reserves: self.real_no_sol_reserves,
+                real_token_reserves: self.real_no_token_reserves,
+            },
+            _ => Curve {
+                virtual_sol_reserves: self.virtual_yes_sol_reserves,
+                virtual_token_reserves: self.virtual_yes_token_reserves,
+                real_sol_reserves: self.real_yes_sol_reserves,
+                real_token_reserves: self.real_yes_token_reserves,
+            },
+        }
+    }
+
+    pub fn set_curve(&mut self, token_type: u8, curve: Curve) {
+        match token_type {
+            0 => {
+                self.virtual_no_sol_reserves = curve.virtual_sol_reserves;
+                self.virtual_no_token_reserves = curve.virtual_token_reserves;
+                self.real_no_sol_reserves = curve.real_sol_reserves;
+                self.real_no_token_reserves = curve.real_token_reserves;
+            }
+            _ => {
+                self.virtual_yes_sol_reserves = curve.virtual_sol_reserves;
+                self.virtual_yes_token_reserves = curve.virtual_token_reserves;
+                self.real_yes_sol_reserves = curve.real_sol_reserves;
+                self.real_yes_token_reserves = curve.real_token_reserves;
+            }
+        }
+    }
+}
+
+//  optional buy executed on market creation, see create_market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InitialBuy {
+    pub token_type: u8,
+    pub amount: u64,
+    pub minimum_receive_amount: u64,
 }
 
 pub trait MarketAccount<'info> {
+    #[allow(clippy::too_many_arguments)]
     fn swap(
         &mut self,
         global_config: &Account<'info, Config>,
 
-        yes_token: &Account<'info, Mint>,
+        yes_token: &AccountInfo<'info>,
         global_yes_ata: &mut AccountInfo<'info>,
         user_yes_ata: &mut AccountInfo<'info>,
 
-        no_token: &Account<'info, Mint>,
+        no_token: &AccountInfo<'info>,
         global_no_ata: &mut AccountInfo<'info>,
         user_no_ata: &mut AccountInfo<'info>,
 
@@ -67,31 +431,13 @@ pub trait MarketAccount<'info> {
         user: &Signer<'info>,
         signer: &[&[&[u8]]],
 
-        is_small_fee: bool,
+        fee_tier: Option<u8>,
+        is_whitelisted: bool,
+        position: Option<&mut Account<'info, Position>>,
 
-        token_program: &Program<'info, Token>,
-        system_program: &Program<'info, System>,
+        token_program: &Interface<'info, TokenInterface>,
+        collateral: &Collateral<'_, 'info>,
     ) -> Result<()>;
-
-    fn apply_sell(&mut self, token_amount: u64, token_type: u8) -> Option<SellResult>;
-
-    fn apply_buy(&mut self, sol_amount: u64, token_type: u8) -> Option<BuyResult>;
-
-    fn get_sol_for_sell_tokens(&self, token_amount: u64, token_type: u8) -> Option<u64>;
-
-    fn get_tokens_for_buy_sol(&self, sol_amount: u64, token_type: u8) -> Option<u64>;
-
-    fn check_update_real_sol_reserves(
-        &mut self,
-        token_type: u8,
-        global_config: &Account<'info, Config>,
-    ) -> Option<u64>;
-
-    fn calc_expected_real_sol_reserves(
-        &mut self,
-        token_type: u8,
-        global_config: &Account<'info, Config>,
-    ) -> Option<u64>;
 }
 
 impl<'info> MarketAccount<'info> for Account<'info, Market> {
@@ -99,11 +445,11 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
         &mut self,
         global_config: &Account<'info, Config>,
 
-        yes_token: &Account<'info, Mint>,
+        yes_token: &AccountInfo<'info>,
         global_yes_ata: &mut AccountInfo<'info>,
         user_yes_ata: &mut AccountInfo<'info>,
 
-        no_token: &Account<'info, Mint>,
+        no_token: &AccountInfo<'info>,
         global_no_ata: &mut AccountInfo<'info>,
         user_no_ata: &mut AccountInfo<'info>,
 
@@ -120,324 +466,153 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
         user: &Signer<'info>,
         signer: &[&[&[u8]]],
 
-        is_small_fee: bool,
+        fee_tier: Option<u8>,
+        is_whitelisted: bool,
+        position: Option<&mut Account<'info, Position>>,
 
-        token_program: &Program<'info, Token>,
-        system_program: &Program<'info, System>,
+        token_program: &Interface<'info, TokenInterface>,
+        collateral: &Collateral<'_, 'info>,
     ) -> Result<()> {
-        if amount <= 0 {
+        require!(
+            !global_config.is_paused(Config::PAUSE_SWAP),
+            TakesFunError::ProgramPaused
+        );
+        require!(!self.frozen, TakesFunError::MarketFrozen);
+        require!(
+            !self.is_completed && !self.is_side_completed(token_type),
+            TakesFunError::MarketAlreadyCompleted
+        );
+        require!(!self.resolved, TakesFunError::MarketResolved);
+        require!(
+            !self.has_resolution_proposal(),
+            TakesFunError::ResolutionAlreadyProposed
+        );
+        require!(self.has_market_vault, TakesFunError::MarketVaultNotMigrated);
+
+        if amount == 0 {
             return err!(TakesFunError::InvalidAmount);
         }
 
-        let sol_amount: u64;
-        let token_amount: u64;
-        let platform_fee_lamports: u64;
-        let creator_fee_lamports: u64;
         let mut actual_shift_lamports_real: u64;
         let mut actual_shift_lamports_virtual: u64;
         actual_shift_lamports_virtual = 0;
         actual_shift_lamports_real = 0;
 
+        let mut curve = self.curve(token_type);
+
         //check the real_sol_reserves is enough
-        let real_sol_reserves_enough = self
-            .check_update_real_sol_reserves(token_type, global_config)
+        let real_sol_reserves_enough = curve
+            .check_update_real_sol_reserves(
+                global_config.initial_virtual_token_reserves(token_type),
+            )
             .ok_or(TakesFunError::InsufficientRealSolReserves)?;
 
         msg!("real_sol_reserves_enough: {}", real_sol_reserves_enough);
 
-        if direction == 1 {
-            //Sell tokens
-
-            let sell_result = self
-                .apply_sell(amount, token_type)
-                .ok_or(TakesFunError::SellFailed)?;
-            msg!("SellResult: {:#?}", sell_result);
-
-            sol_amount = sell_result.sol_amount;
-            token_amount = sell_result.token_amount;
-
-            if is_small_fee == false {
-                platform_fee_lamports =
-                    bps_mul(global_config.platform_sell_fee, sol_amount, 10_000).unwrap();
-                msg!("Platform Fee: {} SOL", platform_fee_lamports);
-            } else {
-                platform_fee_lamports =
-                    bps_mul(global_config.platform_sell_small_fee, sol_amount, 10_000).unwrap();
-                msg!("Platform small Fee: {} SOL", platform_fee_lamports);
-            }
-
-            creator_fee_lamports =
-                bps_mul(global_config.creator_sell_fee, sol_amount, 10_000).unwrap();
-            msg!("Creator Fee: {} SOL", creator_fee_lamports);
-
-            //complete sell
-            let sell_amount_minus_fee =
-                sell_result.sol_amount - platform_fee_lamports - creator_fee_lamports;
-
-            require!(
-                sell_amount_minus_fee >= minimum_receive_amount,
-                TakesFunError::SlippageExceeded,
-            );
-
-            if token_type == 0 {
-                // Transfer no tokens to market
-                token_transfer_user(
-                    user_no_ata.clone(),
-                    &user,
-                    global_no_ata.clone(),
-                    &token_program,
-                    sell_result.token_amount,
-                )?;
-                msg!("no Token to market transfer complete");
-            } else {
-                // Transfer yes tokens to market
-                token_transfer_user(
-                    user_yes_ata.clone(),
-                    &user,
-                    global_yes_ata.clone(),
-                    &token_program,
-                    sell_result.token_amount,
-                )?;
-                msg!("yes Token to market transfer complete");
-            }
-
-            // Transfer SOL to user
-            sol_transfer_with_signer(
-                source.clone(),
-                user.to_account_info(),
-                &system_program,
-                signer,
-                sell_amount_minus_fee,
-            )?;
-            msg!("SOL to user transfer complete");
-
-            if platform_fee_lamports > 0 {
-                //Transfer SOL to team_wallet
-                sol_transfer_with_signer(
-                    source.clone(),
-                    team_wallet.clone(),
-                    &system_program,
-                    signer,
-                    platform_fee_lamports,
-                )?;
-
-                msg!("Fee to team_wallet transfer complete");
-            }
-
-            if creator_fee_lamports > 0 {
-                //Transfer SOL to creator_wallet
-                sol_transfer_with_signer(
-                    source.clone(),
-                    creator_vault.clone(),
-                    &system_program,
-                    signer,
-                    creator_fee_lamports,
-                )?;
-
-                msg!("Fee to creator_wallet transfer complete");
-            }
-        } else {
-            let buy_amount_applied: u64;
-
-            if is_small_fee == false {
-                platform_fee_lamports =
-                    bps_mul(global_config.platform_buy_fee, amount, 10_000).unwrap();
-                msg!("Platform Fee: {} SOL", platform_fee_lamports);
-            } else {
-                platform_fee_lamports =
-                    bps_mul(global_config.platform_buy_small_fee, amount, 10_000).unwrap();
-                msg!("Platform small Fee: {} SOL", platform_fee_lamports);
-            }
-
-            creator_fee_lamports = bps_mul(global_config.creator_buy_fee, amount, 10_000).unwrap();
-            msg!("Creator Fee: {} SOL", creator_fee_lamports);
-
-            buy_amount_applied = amount - platform_fee_lamports - creator_fee_lamports;
-
-            // Buy tokens
-            let buy_result = self
-                .apply_buy(buy_amount_applied, token_type)
-                .ok_or(TakesFunError::BuyFailed)?;
-
-            msg!("BuyResult: {:#?}", buy_result);
-
-            sol_amount = buy_result.sol_amount;
-            token_amount = buy_result.token_amount;
-
-            //complete buy
-            require!(
-                buy_result.token_amount >= minimum_receive_amount,
-                TakesFunError::SlippageExceeded,
+        let side = match token_type {
+            0 => SwapSide::new(no_token.clone(), global_no_ata.clone(), user_no_ata.clone())?,
+            _ => SwapSide::new(
+                yes_token.clone(),
+                global_yes_ata.clone(),
+                user_yes_ata.clone(),
+            )?,
+        };
+        let side_supply = side.supply(global_config);
+        let swap_result = curve.swap(
+            global_config,
+            side,
+            source,
+            team_wallet,
+            creator_vault,
+            amount,
+            direction,
+            minimum_receive_amount,
+            fee_tier,
+            user,
+            signer,
+            token_program,
+            collateral,
+        )?;
+        self.set_curve(token_type, curve);
+
+        let sol_amount = swap_result.sol_amount;
+        let token_amount = swap_result.token_amount;
+        let platform_fee_lamports = swap_result.platform_fee_lamports;
+        let creator_fee_lamports = swap_result.creator_fee_lamports;
+
+        if direction != 1 {
+            // Cross effect on buy only, the other side gives up virtual and real SOL
+            let other_type = 1 - token_type.min(1);
+            let mut other = self.curve(other_type);
+            (actual_shift_lamports_virtual, actual_shift_lamports_real) = other
+                .remove_liquidity(
+                    global_config.cross_shift(sol_amount),
+                    global_config.initial_virtual_token_reserves(other_type),
+                    global_config.min_sol_liquidity,
+                )
+                .ok_or(TakesFunError::InvalidExpectedRealSolReserves)?;
+            self.set_curve(other_type, other);
+            msg!(
+                "actual_shift virtual: {:?}, real: {:?}",
+                actual_shift_lamports_virtual,
+                actual_shift_lamports_real
             );
 
-            msg!("swap: token_type:{:?}", token_type);
-            // Transfer tokens to user
-            if token_type == 0 {
-                token_transfer_with_signer(
-                    global_no_ata.clone(),
-                    source.clone(),
-                    user_no_ata.clone(),
-                    &token_program,
-                    signer,
-                    buy_result.token_amount,
-                )?;
-            } else {
-                token_transfer_with_signer(
-                    global_yes_ata.clone(),
-                    source.clone(),
-                    user_yes_ata.clone(),
-                    &token_program,
-                    signer,
-                    buy_result.token_amount,
-                )?;
-            }
-            msg!("Token transfer complete");
-
-            //Transfer sol to market
-            sol_transfer_from_user(
-                &user,
-                source.clone(),
-                &system_program,
-                buy_result.sol_amount,
-            )?;
-            msg!("SOL to bonding curve transfer complete");
-
-            if platform_fee_lamports > 0 {
+            if actual_shift_lamports_real > 0 {
                 //Transfer SOL to team_wallet
-                sol_transfer_from_user(
-                    &user,
-                    team_wallet.clone(),
-                    &system_program,
-                    platform_fee_lamports,
-                )?;
-                // sol_transfer_with_signer(
-                //     source.clone(),
-                //     team_wallet.clone(),
-                //     &system_program,
-                //     signer,
-                //     platform_fee_lamports,
-                // )?;
+                collateral.transfer_from_vault(team_wallet2.clone(), actual_shift_lamports_real)?;
 
                 msg!("Fee to team_wallet transfer complete");
             }
+        }
 
-            if creator_fee_lamports > 0 {
-                //Transfer SOL to creator_wallet
-                sol_transfer_from_user(
-                    &user,
-                    creator_vault.clone(),
-                    &system_program,
-                    creator_fee_lamports,
+        let wallet_sol_spent = match position {
+            Some(position) => {
+                if position.user == Pubkey::default() {
+                    position.market = self.key();
+                    position.user = user.key();
+                }
+                position.apply_swap(
+                    self,
+                    direction,
+                    token_type,
+                    sol_amount,
+                    token_amount,
+                    swap_result.fee_lamports(),
+                    side_supply,
+                    token_balance(match token_type {
+                        0 => user_no_ata,
+                        _ => user_yes_ata,
+                    })?,
                 )?;
-
-                // sol_transfer_with_signer(
-                //     source.clone(),
-                //     creator.clone(),
-                //     &system_program,
-                //     signer,
-                //     creator_fee_lamports,
-                // )?;
-
-                msg!("Fee to creator_wallet transfer complete");
+                Some(position.total_sol_spent)
             }
-
-            // Cross effect on buy only
-            let shift_sol = convert_from_float(
-                convert_to_float(buy_result.sol_amount, 9) * global_config.cross_sol_factor,
-                9,
-            );
-
-            msg!("self.real_yes_sol_reserves: {:?} self.real_no_sol_reserves: {:?} global_config.min_sol_liquidity: {:?}", self.real_yes_sol_reserves, self.real_no_sol_reserves, global_config.min_sol_liquidity);
-
-            // Determine the maximum removable SOL
-            let max_can_remove_virtual = match token_type {
-                0 => self
-                    .virtual_yes_sol_reserves
-                    .saturating_sub(global_config.min_sol_liquidity),
-                _ => self
-                    .virtual_no_sol_reserves
-                    .saturating_sub(global_config.min_sol_liquidity),
-            };
-            msg!(
-                "shift_sol: {:?}, max_can_remove: {:?}",
-                shift_sol,
-                max_can_remove_virtual
-            );
-
-            // Set actual shift amount
-            actual_shift_lamports_virtual = shift_sol.min(max_can_remove_virtual);
-            msg!("actual_shift: {:?}", actual_shift_lamports_virtual);
-
-            match token_type {
-                1 => {
-                    self.virtual_no_sol_reserves = self
-                        .virtual_no_sol_reserves
-                        .saturating_sub(actual_shift_lamports_virtual);
-                }
-                _ => {
-                    self.virtual_yes_sol_reserves = self
-                        .virtual_yes_sol_reserves
-                        .saturating_sub(actual_shift_lamports_virtual);
-                }
+            None => {
+                require!(
+                    !self.position_limits().is_limited(),
+                    TakesFunError::MissingPositionAccount
+                );
+                None
             }
+        };
 
-            //Determine the expected Real Sol Reserves
-            let expected_real_sol_reserves = self
-                .calc_expected_real_sol_reserves(token_type, global_config)
-                .ok_or(TakesFunError::InvalidExpectedRealSolReserves)?;
-
-            let expected_shift_lamports_real = match token_type {
-                0 => self
-                    .real_yes_sol_reserves
-                    .saturating_sub(expected_real_sol_reserves),
-                _ => self
-                    .real_no_sol_reserves
-                    .saturating_sub(expected_real_sol_reserves),
-            };
-
-            // Determine the maximum removable SOL
-            let max_can_remove_real = match token_type {
-                0 => self
-                    .real_yes_sol_reserves
-                    .saturating_sub(global_config.min_sol_liquidity),
-                _ => self
-                    .real_no_sol_reserves
-                    .saturating_sub(global_config.min_sol_liquidity),
-            };
-            msg!(
-                "shift_sol: {:?}, max_can_remove: {:?}",
-                shift_sol,
-                max_can_remove_real
-            );
-
-            // Set actual shift amount
-            actual_shift_lamports_real = expected_shift_lamports_real.min(max_can_remove_real);
-            msg!("actual_shift: {:?}", actual_shift_lamports_real);
+        let timestamp = Clock::get()?.unix_timestamp;
+        if direction != 1 {
+            self.check_launch_phase(timestamp, amount, is_whitelisted, wallet_sol_spent)?;
+        }
+        if self.first_trade_at == 0 {
+            self.first_trade_at = timestamp;
+        }
 
+        let real_sol_reserves = match token_type {
+            0 => self.real_no_sol_reserves,
+            _ => self.real_yes_sol_reserves,
+        };
+        let completed = direction != 1 && global_config.is_graduated(real_sol_reserves);
+        if completed {
             match token_type {
-                1 => {
-                    self.real_no_sol_reserves = self
-                        .real_no_sol_reserves
-                        .saturating_sub(actual_shift_lamports_real);
-                }
-                _ => {
-                    self.real_yes_sol_reserves = self
-                        .real_yes_sol_reserves
-                        .saturating_sub(actual_shift_lamports_real);
-                }
-            }
-
-            if actual_shift_lamports_real > 0 {
-                //Transfer SOL to team_wallet
-                sol_transfer_with_signer(
-                    source.clone(),
-                    team_wallet2.clone(),
-                    &system_program,
-                    signer,
-                    actual_shift_lamports_real,
-                )?;
-
-                msg!("Fee to team_wallet transfer complete");
+                0 => self.no_completed = true,
+                _ => self.yes_completed = true,
             }
         }
 
@@ -447,16 +622,16 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
             yes_token: yes_token.key(),
             market: self.key(),
 
-            sol_amount: sol_amount,
-            token_amount: token_amount,
-            platform_fee_lamports: platform_fee_lamports,
-            creator_fee_lamports: creator_fee_lamports,
+            sol_amount,
+            token_amount,
+            platform_fee_lamports,
+            creator_fee_lamports,
             shift_lamports_real: actual_shift_lamports_real,
             shift_lamports_virtual: actual_shift_lamports_virtual,
 
-            direction: direction,
-            token_type: token_type,
-            timestamp: Clock::get()?.unix_timestamp,
+            direction,
+            token_type,
+            timestamp,
 
             yes_virtual_reserve_lamport: self.virtual_yes_sol_reserves,
             yes_virtual_reserve_token: self.virtual_yes_token_reserves,
@@ -469,398 +644,308 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
             no_real_reserve_token: self.real_no_token_reserves,
         });
 
-        Ok(())
-    }
-
-    fn get_sol_for_sell_tokens(&self, token_amount: u64, token_type: u8) -> Option<u64> {
-        if token_amount == 0 {
-            return None;
+        if completed {
+            emit!(CompleteEvent {
+                market: self.key(),
+                token_type,
+                real_sol_reserves,
+                timestamp,
+            });
         }
 
-        msg!(
-            "GetSolForSellTokens: token_amount: {} token_type: {}",
-            token_amount,
-            token_type
-        );
-
-        // Select the correct reserves based on token_type
-        let (current_sol, current_tokens) = if token_type == 0 {
-            (
-                self.virtual_no_sol_reserves as u128,
-                self.virtual_no_token_reserves as u128,
-            )
-        } else {
-            (
-                self.virtual_yes_sol_reserves as u128,
-                self.virtual_yes_token_reserves as u128,
-            )
-        };
-
-        // Convert to a common decimal basis (using 9 decimals as base)
-        let current_tokens = current_tokens
-            .checked_mul(1_000_000_000)? // Scale tokens up to 9 decimals
-            .checked_div(1_000_000)?; // Adjust from 6 decimals
-
-        // Calculate new reserves using constant product formula
-        let new_tokens = current_tokens.checked_add(
-            (token_amount as u128)
-                .checked_mul(1_000_000_000)? // Scale input tokens to 9 decimals
-                .checked_div(1_000_000)?, // From 6 decimals
-        )?;
-
-        let new_sol = (current_sol.checked_mul(current_tokens)?).checked_div(new_tokens)?;
+        Ok(())
+    }
+}
 
-        let sol_out = current_sol.checked_sub(new_sol)?;
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
 
-        <u128 as TryInto<u64>>::try_into(sol_out).ok()
+    //  current layout market with every field zeroed
+    pub(crate) fn test_market() -> Market {
+        let data = vec![0u8; Market::INIT_SPACE];
+        Market::try_deserialize_unchecked(&mut [&[0u8; 8][..], &data].concat().as_slice()).unwrap()
     }
 
-    fn get_tokens_for_buy_sol(&self, sol_amount: u64, token_type: u8) -> Option<u64> {
-        if sol_amount == 0 {
-            return None;
-        }
-        msg!(
-            "GetTokensForBuySol: sol_amount: {} token_type: {}",
-            sol_amount,
-            token_type
-        );
+    //  market layout of the first release, allocated with space = 320
+    #[derive(AnchorSerialize)]
+    struct LegacyMarket {
+        yes_token_mint: Pubkey,
+        no_token_mint: Pubkey,
+        creator: Pubkey,
+        real_yes_token_reserves: u64,
+        real_yes_sol_reserves: u64,
+        virtual_yes_sol_reserves: u64,
+        virtual_yes_token_reserves: u64,
+        virtual_no_sol_reserves: u64,
+        virtual_no_token_reserves: u64,
+        real_no_token_reserves: u64,
+        real_no_sol_reserves: u64,
+        is_completed: bool,
+        market_info: String,
+    }
 
-        // Select the correct reserves based on token_type
-        let (current_sol, current_tokens) = if token_type == 0 {
-            (
-                self.virtual_no_sol_reserves as u128,
-                self.virtual_no_token_reserves as u128,
-            )
-        } else {
-            (
-                self.virtual_yes_sol_reserves as u128,
-                self.virtual_yes_token_reserves as u128,
-            )
+    const LEGACY_SPACE: usize = 320;
+
+    fn legacy_account(market_info: &str) -> (LegacyMarket, Vec<u8>) {
+        let legacy = LegacyMarket {
+            yes_token_mint: Pubkey::new_unique(),
+            no_token_mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            real_yes_token_reserves: 1,
+            real_yes_sol_reserves: 2,
+            virtual_yes_sol_reserves: 3,
+            virtual_yes_token_reserves: 4,
+            virtual_no_sol_reserves: 5,
+            virtual_no_token_reserves: 6,
+            real_no_token_reserves: 7,
+            real_no_sol_reserves: 8,
+            is_completed: false,
+            market_info: market_info.to_string(),
         };
+        let mut data = Market::DISCRIMINATOR.to_vec();
+        data.extend(legacy.try_to_vec().unwrap());
+        assert!(data.len() <= LEGACY_SPACE);
+        data.resize(LEGACY_SPACE, 0);
+        (legacy, data)
+    }
 
-        // Convert to a common decimal basis (using 9 decimals as base)
-        let current_tokens = current_tokens
-            .checked_mul(1_000_000_000)? // Scale tokens up to 9 decimals
-            .checked_div(1_000_000)?; // Adjust from 6 decimals
-
-        // Calculate new reserves using constant product formula
-        let new_sol = current_sol.checked_add(sol_amount as u128)?;
-        let new_tokens = (current_sol.checked_mul(current_tokens)?).checked_div(new_sol)?;
-
-        let tokens_out = current_tokens.checked_sub(new_tokens)?;
-
-        // Convert back to 6 decimal places for tokens
-        let tokens_out = tokens_out
-            .checked_mul(1_000_000)? // Convert to 6 decimals
-            .checked_div(1_000_000_000)?; // From 9 decimals
-
-        <u128 as TryInto<u64>>::try_into(tokens_out).ok()
+    #[test]
+    fn legacy_market_does_not_deserialize_without_realloc() {
+        let (_, data) = legacy_account(&"q".repeat(140));
+        assert!(Market::try_deserialize(&mut &data[..]).is_err());
     }
 
-    fn apply_buy(&mut self, sol_amount: u64, token_type: u8) -> Option<BuyResult> {
-        // Computing Token Amount out
-        let token_amount = self.get_tokens_for_buy_sol(sol_amount, token_type)?;
-        msg!(
-            "ApplyBuy: sol_amount: {}, token_amount: {}",
-            sol_amount,
-            token_amount
-        );
+    #[test]
+    fn legacy_market_upgrades_after_realloc() {
+        //  the longest market_info that fit the legacy account
+        let market_info = "q".repeat(LEGACY_SPACE - 8 - 3 * 32 - 8 * 8 - 1 - 4);
+        let (legacy, mut data) = legacy_account(&market_info);
+        data.resize(8 + Market::INIT_SPACE, 0);
+
+        let mut market = Market::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(market.creator, legacy.creator);
+        assert_eq!(market.real_no_sol_reserves, legacy.real_no_sol_reserves);
+        assert_eq!(market.market_info, market_info);
+        assert_eq!(market.version, 0);
+        assert!(market.is_sol_collateral());
+        assert!(!market.has_market_vault);
+        assert_eq!(market.bounds(), (0, 1));
+
+        market.upgrade().unwrap();
+        assert_eq!(market.version, Market::VERSION);
+        assert_eq!(market.payer, legacy.creator);
+        assert_ne!(market.first_trade_at, 0);
+        assert!(market.upgrade().is_err());
+
+        let mut upgraded = vec![0u8; 8 + Market::INIT_SPACE];
+        market.try_serialize(&mut &mut upgraded[..]).unwrap();
+        let market = Market::try_deserialize(&mut &upgraded[..]).unwrap();
+        assert_eq!(market.version, Market::VERSION);
+    }
 
-        // Ensure token_amount is within valid limits
-        let real_reserves = if token_type == 0 {
-            self.real_no_token_reserves
-        } else {
-            self.real_yes_token_reserves
+    #[test]
+    fn launch_phase_reports_each_restriction() {
+        let mut market = test_market();
+        market.created_at = 1_000;
+        market.launch_phase = LaunchPhase {
+            duration: 60,
+            max_buy_sol: 100,
+            max_wallet_sol: 150,
+            whitelist_only: true,
         };
 
-        if token_amount >= real_reserves {
-            //some error
-            msg!(
-                "Error: BuyTokenAmountInvalid - token_amount {} exceeds real_reserves {}",
-                token_amount,
-                real_reserves
-            );
-            return None;
-        }
-
-        msg!(
-            "ApplyBuy: virtual_no_token_reserves: {}, virtual_yes_token_reserves: {}",
-            self.virtual_no_token_reserves,
-            self.virtual_yes_token_reserves
+        assert_eq!(
+            market
+                .check_launch_phase(1_000, 10, false, Some(10))
+                .unwrap_err(),
+            error!(TakesFunError::LaunchPhaseWhitelistOnly)
         );
-
-        // Adjusting token reserve values
-        // New Virtual Token Reserves
-        let new_virtual_token_reserves = if token_type == 0 {
-            self.virtual_no_token_reserves as u128
-        } else {
-            self.virtual_yes_token_reserves as u128
-        }
-        .checked_sub(token_amount as u128)?;
-
-        msg!(
-            "ApplyBuy: new_virtual_token_reserves: {}",
-            new_virtual_token_reserves
+        assert_eq!(
+            market
+                .check_launch_phase(1_000, 101, true, Some(101))
+                .unwrap_err(),
+            error!(TakesFunError::LaunchPhaseMaxBuyExceeded)
         );
-
-        msg!(
-            "ApplyBuy: real_no_token_reserves: {}, real_yes_token_reserves: {}",
-            self.real_no_token_reserves,
-            self.real_yes_token_reserves
+        assert_eq!(
+            market
+                .check_launch_phase(1_000, 100, true, Some(151))
+                .unwrap_err(),
+            error!(TakesFunError::LaunchPhaseMaxWalletExceeded)
         );
+        assert!(market
+            .check_launch_phase(1_059, 100, true, Some(150))
+            .is_ok());
 
-        // New Real Token Reserves
-        let new_real_token_reserves = if token_type == 0 {
-            self.real_no_token_reserves as u128
-        } else {
-            self.real_yes_token_reserves as u128
-        }
-        .checked_sub(token_amount as u128)?;
-
-        msg!(
-            "ApplyBuy: new_virtual_token_reserves: {}",
-            new_real_token_reserves
-        );
-
-        msg!(
-            "ApplyBuy: virtual_no_sol_reserves: {}, virtual_yes_sol_reserves: {}",
-            self.virtual_no_sol_reserves,
-            self.virtual_yes_sol_reserves
-        );
-        // Adjusting sol reserve values
-        // New Virtual Sol Reserves
-        let new_virtual_sol_reserves = if token_type == 0 {
-            self.virtual_no_sol_reserves as u128
-        } else {
-            self.virtual_yes_sol_reserves as u128
-        }
-        .checked_add(sol_amount as u128)?;
+        //  restrictions end with the phase
+        assert!(market.check_launch_phase(1_060, 1_000, false, None).is_ok());
+    }
 
-        msg!(
-            "ApplyBuy: new_virtual_sol_reserves: {}",
-            new_virtual_sol_reserves
-        );
+    fn funded_market(yes_sol: u64, no_sol: u64) -> Market {
+        let mut market = test_market();
+        market.real_yes_sol_reserves = yes_sol;
+        market.real_no_sol_reserves = no_sol;
+        market
+    }
 
-        msg!(
-            "ApplyBuy: real_no_sol_reserves: {}, real_yes_sol_reserves: {}",
-            self.real_no_sol_reserves,
-            self.real_yes_sol_reserves
+    #[test]
+    fn binary_resolution_pays_the_winning_side() {
+        let mut market = funded_market(600, 400);
+        market.set_bounds(None).unwrap();
+        assert_eq!(
+            market.resolve(2, 10, 10, u64::MAX).unwrap_err(),
+            error!(TakesFunError::ValueInvalid)
         );
-        // New Real Sol Reserves
-        let new_real_sol_reserves = if token_type == 0 {
-            self.real_no_sol_reserves as u128
-        } else {
-            self.real_yes_sol_reserves as u128
-        }
-        .checked_add(sol_amount as u128)?;
 
-        msg!("ApplyBuy: new_real_sol_reserves: {}", new_real_sol_reserves);
+        market.resolve(1, 10, 10, u64::MAX).unwrap();
+        assert_eq!(market.yes_payout_pool, 1_000);
+        assert_eq!(market.no_payout_pool, 0);
+        assert_eq!(market.redemption_amount(1, 5), Some(500));
+        assert_eq!(market.redemption_amount(0, 5), Some(0));
+    }
 
-        if token_type == 0 {
-            self.virtual_no_token_reserves = new_virtual_token_reserves.try_into().ok()?;
-            self.real_no_token_reserves = new_real_token_reserves.try_into().ok()?;
-            self.virtual_no_sol_reserves = new_virtual_sol_reserves.try_into().ok()?;
-            self.real_no_sol_reserves = new_real_sol_reserves.try_into().ok()?;
-        } else {
-            self.virtual_yes_token_reserves = new_virtual_token_reserves.try_into().ok()?;
-            self.real_yes_token_reserves = new_real_token_reserves.try_into().ok()?;
-            self.virtual_yes_sol_reserves = new_virtual_sol_reserves.try_into().ok()?;
-            self.real_yes_sol_reserves = new_real_sol_reserves.try_into().ok()?;
+    #[test]
+    fn scalar_resolution_is_linear_and_clamped_to_the_bounds() {
+        let mut market = funded_market(500, 500);
+        market
+            .set_bounds(Some(ScalarBounds {
+                lower_bound: -100,
+                upper_bound: 300,
+            }))
+            .unwrap();
+
+        market.resolve(0, 10, 10, u64::MAX).unwrap();
+        assert_eq!(market.yes_payout_pool, 250);
+        assert_eq!(market.no_payout_pool, 750);
+        assert_eq!(market.sol_liabilities(), Some(1_000));
+
+        for (value, clamped, yes_payout_pool) in [(-500, -100, 0), (1_000, 300, 1_000)] {
+            let mut market = funded_market(500, 500);
+            market
+                .set_bounds(Some(ScalarBounds {
+                    lower_bound: -100,
+                    upper_bound: 300,
+                }))
+                .unwrap();
+            market.resolve(value, 10, 10, u64::MAX).unwrap();
+            assert_eq!(market.resolved_value, clamped);
+            assert_eq!(market.yes_payout_pool, yes_payout_pool);
+            assert_eq!(market.no_payout_pool, 1_000 - yes_payout_pool);
         }
-
-        Some(BuyResult {
-            token_amount,
-            sol_amount,
-        })
     }
 
-    fn apply_sell(&mut self, token_amount: u64, token_type: u8) -> Option<SellResult> {
-        msg!(
-            "apply_sell: token_amount: {} token_type: {}",
-            token_amount,
-            token_type
-        );
-
-        // Computing Sol Amount out
-        let sol_amount = self.get_sol_for_sell_tokens(token_amount, token_type)?;
-        msg!("apply_sell: sol_amount: {}", sol_amount);
+    #[test]
+    fn empty_side_share_goes_to_the_other_side() {
+        let mut market = funded_market(500, 500);
+        market
+            .set_bounds(Some(ScalarBounds {
+                lower_bound: 0,
+                upper_bound: 4,
+            }))
+            .unwrap();
+        market.resolve(1, 0, 10, u64::MAX).unwrap();
+        assert_eq!(market.yes_payout_pool, 0);
+        assert_eq!(market.no_payout_pool, 1_000);
+        assert_eq!(market.redemption_amount(1, 1), None);
+        assert_eq!(market.redemption_amount(0, 10), Some(1_000));
+    }
 
-        msg!(
-            "ApplyBuy: virtual_no_token_reserves: {}, virtual_yes_token_reserves: {}",
-            self.virtual_no_token_reserves,
-            self.virtual_yes_token_reserves
-        );
+    #[test]
+    fn payout_pools_are_bounded_by_the_vault() {
+        let mut market = funded_market(600, 400);
+        market
+            .set_bounds(Some(ScalarBounds {
+                lower_bound: 0,
+                upper_bound: 2,
+            }))
+            .unwrap();
+        market.resolve(1, 10, 10, 700).unwrap();
+        assert_eq!(market.yes_payout_pool + market.no_payout_pool, 700);
+        assert_eq!(market.yes_payout_pool, 350);
+
+        //  redeeming every token never pays more than the vault holds
+        let paid =
+            market.redemption_amount(1, 10).unwrap() + market.redemption_amount(0, 10).unwrap();
+        assert!(paid <= 700);
+    }
 
-        // Adjusting token reserve values
-        // New Virtual Token Reserves
-        let new_virtual_token_reserves = if token_type == 0 {
-            self.virtual_no_token_reserves as u128
-        } else {
-            self.virtual_yes_token_reserves as u128
-        }
-        .checked_add(token_amount as u128)?;
-        msg!(
-            "apply_sell: new_virtual_token_reserves: {}",
-            new_virtual_token_reserves
-        );
+    #[test]
+    fn resolution_waits_for_the_challenge_period() {
+        let mut market = funded_market(600, 400);
+        market.set_bounds(None).unwrap();
+        let proposer = Pubkey::new_unique();
+        let now = 1_000;
 
-        msg!(
-            "ApplyBuy: real_no_token_reserves: {}, real_yes_token_reserves: {}",
-            self.real_no_token_reserves,
-            self.real_yes_token_reserves
+        assert_eq!(
+            market.propose_resolution(3, proposer, now).unwrap_err(),
+            error!(TakesFunError::ValueInvalid)
         );
-        // New Real Token Reserves
-        let new_real_token_reserves = if token_type == 0 {
-            self.real_no_token_reserves as u128
-        } else {
-            self.real_yes_token_reserves as u128
-        }
-        .checked_add(token_amount as u128)?;
-        msg!(
-            "apply_sell: new_real_token_reserves: {}",
-            new_real_token_reserves
+        market.propose_resolution(1, proposer, now).unwrap();
+        assert_eq!(
+            market.propose_resolution(0, proposer, now).unwrap_err(),
+            error!(TakesFunError::ResolutionAlreadyProposed)
         );
 
-        msg!(
-            "ApplyBuy: virtual_no_sol_reserves: {}, virtual_yes_sol_reserves: {}",
-            self.virtual_no_sol_reserves,
-            self.virtual_yes_sol_reserves
-        );
-        // Adjusting sol reserve values
-        // New Virtual Sol Reserves
-        let new_virtual_sol_reserves = if token_type == 0 {
-            self.virtual_no_sol_reserves as u128
-        } else {
-            self.virtual_yes_sol_reserves as u128
-        }
-        .checked_sub(sol_amount as u128)?;
-        msg!(
-            "apply_sell: new_virtual_sol_reserves: {}",
-            new_virtual_sol_reserves
+        let deadline = now + RESOLUTION_CHALLENGE_PERIOD;
+        assert_eq!(market.challenge_deadline(), deadline);
+        assert_eq!(
+            market
+                .finalize_resolution(deadline - 1, 10, 10, u64::MAX)
+                .unwrap_err(),
+            error!(TakesFunError::ChallengePeriodNotElapsed)
         );
 
-        msg!(
-            "real_no_sol_reserves: {}, real_yes_sol_reserves: {}",
-            self.real_no_sol_reserves,
-            self.real_yes_sol_reserves
+        //  a challenged proposal is dropped and a new one restarts the period
+        market.challenge_resolution().unwrap();
+        assert_eq!(
+            market
+                .finalize_resolution(deadline, 10, 10, u64::MAX)
+                .unwrap_err(),
+            error!(TakesFunError::ResolutionNotProposed)
         );
-        // New Real Sol Reserves
-        let new_real_sol_reserves = if token_type == 0 {
-            self.real_no_sol_reserves as u128
-        } else {
-            self.real_yes_sol_reserves as u128
-        }
-        .checked_sub(sol_amount as u128)?;
-        msg!(
-            "apply_sell: new_real_sol_reserves: {}",
-            new_real_sol_reserves
+        market.propose_resolution(0, proposer, deadline).unwrap();
+        market
+            .finalize_resolution(deadline + RESOLUTION_CHALLENGE_PERIOD, 10, 10, u64::MAX)
+            .unwrap();
+        assert!(market.resolved);
+        assert_eq!(market.resolved_value, 0);
+        assert_eq!(market.no_payout_pool, 1_000);
+        assert!(!market.has_resolution_proposal());
+        assert_eq!(
+            market
+                .propose_resolution(1, proposer, deadline)
+                .unwrap_err(),
+            error!(TakesFunError::MarketResolved)
         );
-
-        if token_type == 0 {
-            self.virtual_no_token_reserves = new_virtual_token_reserves.try_into().ok()?;
-            self.real_no_token_reserves = new_real_token_reserves.try_into().ok()?;
-            self.virtual_no_sol_reserves = new_virtual_sol_reserves.try_into().ok()?;
-            self.real_no_sol_reserves = new_real_sol_reserves.try_into().ok()?;
-        } else {
-            self.virtual_yes_token_reserves = new_virtual_token_reserves.try_into().ok()?;
-            self.real_yes_token_reserves = new_real_token_reserves.try_into().ok()?;
-            self.virtual_yes_sol_reserves = new_virtual_sol_reserves.try_into().ok()?;
-            self.real_yes_sol_reserves = new_real_sol_reserves.try_into().ok()?;
-        }
-
-        Some(SellResult {
-            token_amount,
-            sol_amount,
-        })
     }
 
-    fn check_update_real_sol_reserves(
-        &mut self,
-        token_type: u8,
-        global_config: &Account<'info, Config>,
-    ) -> Option<u64> {
-        // Select the correct reserves based on token_type
-        let (current_sol, current_tokens) = if token_type == 0 {
-            (
-                self.virtual_no_sol_reserves as u128,
-                self.virtual_no_token_reserves as u128,
-            )
-        } else {
-            (
-                self.virtual_yes_sol_reserves as u128,
-                self.virtual_yes_token_reserves as u128,
-            )
-        };
-
-        let current_sol_reserves = if token_type == 0 {
-            self.real_no_sol_reserves as u128
-        } else {
-            self.real_yes_sol_reserves as u128
-        };
-
-        let initial_virtual_token_reserves = if token_type == 0 {
-            global_config.initial_virtual_no_token_reserves_config as u128
-        } else {
-            global_config.initial_virtual_yes_token_reserves_config as u128
-        };
-
-        let basic_virtual_sol_reserves = (current_sol.checked_mul(current_tokens)?)
-            .checked_div(initial_virtual_token_reserves)?;
-        let expected_sol_reserves = current_sol.checked_sub(basic_virtual_sol_reserves)?;
-
-        msg!(
-            "check_update_real_sol_reserves :: basic_virtual_sol_reserves: {}, expected_sol_reserves: {} current_sol_reserves: {}",
-            basic_virtual_sol_reserves,
-            expected_sol_reserves,
-            current_sol_reserves
+    #[test]
+    fn graduated_markets_can_not_be_resolved() {
+        let mut market = funded_market(600, 400);
+        market.set_bounds(None).unwrap();
+        market.no_completed = true;
+        assert_eq!(
+            market
+                .propose_resolution(1, Pubkey::new_unique(), 1_000)
+                .unwrap_err(),
+            error!(TakesFunError::MarketGraduated)
         );
-
-        if expected_sol_reserves > current_sol_reserves {
-            msg!("expected_sol_reserves > current_sol_reserves");
-            if token_type == 0 {
-                self.real_no_sol_reserves = expected_sol_reserves.try_into().ok()?;
-            } else {
-                self.real_yes_sol_reserves = expected_sol_reserves.try_into().ok()?;
-            }
-        }
-
-        <u128 as TryInto<u64>>::try_into(expected_sol_reserves).ok()
+        assert!(!market.has_resolution_proposal());
     }
 
-    fn calc_expected_real_sol_reserves(
-        &mut self,
-        token_type: u8,
-        global_config: &Account<'info, Config>,
-    ) -> Option<u64> {
-        // Select the correct reserves based on token_type
-        let (current_sol, current_tokens) = if token_type == 1 {
-            (
-                self.virtual_no_sol_reserves as u128,
-                self.virtual_no_token_reserves as u128,
-            )
-        } else {
-            (
-                self.virtual_yes_sol_reserves as u128,
-                self.virtual_yes_token_reserves as u128,
-            )
-        };
-
-        let initial_virtual_token_reserves = if token_type == 1 {
-            global_config.initial_virtual_no_token_reserves_config as u128
-        } else {
-            global_config.initial_virtual_yes_token_reserves_config as u128
-        };
+    #[test]
+    fn short_vault_migration_writes_down_the_market() {
+        let mut market = test_market();
+        market.real_yes_sol_reserves = 600;
+        market.real_no_sol_reserves = 400;
 
-        let basic_virtual_sol_reserves = (current_sol.checked_mul(current_tokens)?)
-            .checked_div(initial_virtual_token_reserves)?;
-        let expected_sol_reserves = current_sol.checked_sub(basic_virtual_sol_reserves)?;
-
-        msg!(
-            "calc_expected_real_sol_reserves:: basic_virtual_sol_reserves: {}, expected_sol_reserves: {} ",
-            basic_virtual_sol_reserves,
-            expected_sol_reserves,
-        );
+        market.write_down_sol_liabilities(1_000, 1_000).unwrap();
+        assert_eq!(market.sol_liabilities(), Some(1_000));
 
-        <u128 as TryInto<u64>>::try_into(expected_sol_reserves).ok()
+        market.write_down_sol_liabilities(500, 1_000).unwrap();
+        assert_eq!(market.real_yes_sol_reserves, 300);
+        assert_eq!(market.real_no_sol_reserves, 200);
+        assert_eq!(market.sol_liabilities(), Some(500));
     }
 }
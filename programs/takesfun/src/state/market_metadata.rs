@@ -0,0 +1,81 @@
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+pub const MAX_QUESTION_LEN: usize = 200;
+pub const MAX_CATEGORY_LEN: usize = 32;
+pub const MAX_TAGS: usize = 5;
+pub const MAX_TAG_LEN: usize = 24;
+pub const MAX_RESOLUTION_SOURCE_LEN: usize = 200;
+pub const MAX_RESOLUTION_CRITERIA_LEN: usize = 500;
+
+//  discovery data of a market, seeded by [MARKET_METADATA, market]
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MarketMetadata {
+    pub market: Pubkey,
+
+    #[max_len(MAX_QUESTION_LEN)]
+    pub question: String,
+    #[max_len(MAX_CATEGORY_LEN)]
+    pub category: String,
+    #[max_len(MAX_TAGS, MAX_TAG_LEN)]
+    pub tags: Vec<String>,
+
+    //  url of the source the market resolves from
+    #[max_len(MAX_RESOLUTION_SOURCE_LEN)]
+    pub resolution_source: String,
+    #[max_len(MAX_RESOLUTION_CRITERIA_LEN)]
+    pub resolution_criteria: String,
+
+    pub updated_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MarketMetadataParams {
+    pub question: String,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub resolution_source: String,
+    pub resolution_criteria: String,
+}
+
+fn validate_len(name: &str, value: &str, max_len: usize) -> Result<()> {
+    if value.len() > max_len {
+        msg!(
+            "{} is {} bytes, expected at most {}",
+            name,
+            value.len(),
+            max_len
+        );
+        return err!(TakesFunError::InvalidMarketMetadata);
+    }
+    Ok(())
+}
+
+impl MarketMetadataParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.question.is_empty(),
+            TakesFunError::InvalidMarketMetadata
+        );
+        validate_len("question", &self.question, MAX_QUESTION_LEN)?;
+        validate_len("category", &self.category, MAX_CATEGORY_LEN)?;
+        require!(
+            self.tags.len() <= MAX_TAGS,
+            TakesFunError::InvalidMarketMetadata
+        );
+        for tag in self.tags.iter() {
+            validate_len("tag", tag, MAX_TAG_LEN)?;
+        }
+        validate_len(
+            "resolution_source",
+            &self.resolution_source,
+            MAX_RESOLUTION_SOURCE_LEN,
+        )?;
+        validate_len(
+            "resolution_criteria",
+            &self.resolution_criteria,
+            MAX_RESOLUTION_CRITERIA_LEN,
+        )
+    }
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+//  left behind by close_market, seeded by [MARKET_TOMBSTONE, market]. markets seeded by their
+//  question can not be created again at a closed address, so a new market never inherits the
+//  positions and metadata of the closed one
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MarketTombstone {
+    pub market: Pubkey,
+    pub closed_at: i64,
+}
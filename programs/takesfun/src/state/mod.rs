@@ -1,3 +1,9 @@
+pub mod categorical_market;
 pub mod config;
+pub mod config_change;
+pub mod curve;
 pub mod market;
+pub mod market_metadata;
+pub mod market_tombstone;
+pub mod position;
 pub mod whitelist;
@@ -0,0 +1,373 @@
+use crate::errors::*;
+use crate::state::categorical_market::{CategoricalMarket, MAX_OUTCOMES};
+use crate::state::market::Market;
+use crate::utils::mul_div;
+use anchor_lang::prelude::*;
+
+//  per user per market position, seeded by [POSITION, market, user]
+#[account]
+#[derive(InitSpace, Debug, Default)]
+pub struct Position {
+    pub market: Pubkey,
+    pub user: Pubkey,
+
+    //  SOL put into the curve minus SOL taken out for tokens bought through it, never below zero
+    pub net_sol_in: u64,
+
+    //  tokens bought through the curve minus tokens sold back
+    pub yes_tokens: u64,
+    pub no_tokens: u64,
+
+    //  cost basis, SOL paid for buys and SOL received from sells, fees included
+    pub total_sol_spent: u64,
+    pub total_sol_received: u64,
+    //  platform and creator fees paid on all swaps
+    pub fees_paid: u64,
+}
+
+//  per wallet limits of a market, 0 means no limit
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PositionLimits {
+    pub max_wallet_sol: u64,
+    pub max_wallet_share_bps: u64,
+}
+
+impl PositionLimits {
+    pub fn is_limited(&self) -> bool {
+        self.max_wallet_sol > 0 || self.max_wallet_share_bps > 0
+    }
+}
+
+//  the part of a position a swap changes, tokens are those of the traded side,
+//  shared by binary and categorical positions
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PositionSwap {
+    pub net_sol_in: u64,
+    pub tokens: u64,
+    pub total_sol_spent: u64,
+    pub total_sol_received: u64,
+    pub fees_paid: u64,
+}
+
+impl PositionSwap {
+    //  record a swap and check the position limits on buys,
+    //  sol_amount is the curve side of the swap, side_supply the minted supply of the traded side
+    //  and balance the token balance of the wallet after the swap
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &mut self,
+        limits: PositionLimits,
+        direction: u8,
+        sol_amount: u64,
+        token_amount: u64,
+        fee_lamports: u64,
+        side_supply: u64,
+        balance: u64,
+    ) -> Result<()> {
+        self.fees_paid = self
+            .fees_paid
+            .checked_add(fee_lamports)
+            .ok_or(TakesFunError::OverflowOrUnderflowOccurred)?;
+
+        if direction == 1 {
+            self.total_sol_received = self
+                .total_sol_received
+                .checked_add(sol_amount.saturating_sub(fee_lamports))
+                .ok_or(TakesFunError::OverflowOrUnderflowOccurred)?;
+            //  tokens received from other wallets are sold first, only selling tokens bought
+            //  through the curve frees room so transfers can not reset the position
+            let own_tokens = self.tokens.saturating_sub(balance).min(token_amount);
+            let own_sol = mul_div(sol_amount, own_tokens, token_amount).unwrap_or_default();
+            self.net_sol_in = self.net_sol_in.saturating_sub(own_sol);
+            self.tokens -= own_tokens;
+            return Ok(());
+        }
+
+        self.total_sol_spent = self
+            .total_sol_spent
+            .checked_add(sol_amount)
+            .and_then(|spent| spent.checked_add(fee_lamports))
+            .ok_or(TakesFunError::OverflowOrUnderflowOccurred)?;
+        self.net_sol_in = self
+            .net_sol_in
+            .checked_add(sol_amount)
+            .ok_or(TakesFunError::OverflowOrUnderflowOccurred)?;
+        self.tokens = self
+            .tokens
+            .checked_add(token_amount)
+            .ok_or(TakesFunError::OverflowOrUnderflowOccurred)?;
+
+        if limits.max_wallet_sol > 0 {
+            require!(
+                self.net_sol_in <= limits.max_wallet_sol,
+                TakesFunError::PositionLimitExceeded
+            );
+        }
+        if limits.max_wallet_share_bps > 0 {
+            require!(
+                (balance as u128) * 10_000
+                    <= (side_supply as u128) * (limits.max_wallet_share_bps as u128),
+                TakesFunError::PositionLimitExceeded
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Position {
+    pub fn tokens(&self, token_type: u8) -> u64 {
+        match token_type {
+            0 => self.no_tokens,
+            _ => self.yes_tokens,
+        }
+    }
+
+    //  record a swap and check the market position limits on buys, see PositionSwap::apply
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_swap(
+        &mut self,
+        market: &Market,
+        direction: u8,
+        token_type: u8,
+        sol_amount: u64,
+        token_amount: u64,
+        fee_lamports: u64,
+        side_supply: u64,
+        balance: u64,
+    ) -> Result<()> {
+        let mut swap = PositionSwap {
+            net_sol_in: self.net_sol_in,
+            tokens: self.tokens(token_type),
+            total_sol_spent: self.total_sol_spent,
+            total_sol_received: self.total_sol_received,
+            fees_paid: self.fees_paid,
+        };
+        swap.apply(
+            market.position_limits(),
+            direction,
+            sol_amount,
+            token_amount,
+            fee_lamports,
+            side_supply,
+            balance,
+        )?;
+
+        self.net_sol_in = swap.net_sol_in;
+        self.total_sol_spent = swap.total_sol_spent;
+        self.total_sol_received = swap.total_sol_received;
+        self.fees_paid = swap.fees_paid;
+        match token_type {
+            0 => self.no_tokens = swap.tokens,
+            _ => self.yes_tokens = swap.tokens,
+        }
+
+        Ok(())
+    }
+}
+
+//  per user per categorical market position, seeded by [POSITION, market, user]
+#[account]
+#[derive(InitSpace, Debug, Default)]
+pub struct CategoricalPosition {
+    pub market: Pubkey,
+    pub user: Pubkey,
+
+    //  SOL put into the curves minus SOL taken out for tokens bought through them,
+    //  never below zero
+    pub net_sol_in: u64,
+
+    //  tokens of each outcome bought through the curves minus tokens sold back
+    pub outcome_tokens: [u64; MAX_OUTCOMES],
+
+    //  cost basis, SOL paid for buys and SOL received from sells, fees included
+    pub total_sol_spent: u64,
+    pub total_sol_received: u64,
+    //  platform and creator fees paid on all swaps
+    pub fees_paid: u64,
+}
+
+impl CategoricalPosition {
+    //  record a swap of an outcome and check the market position limits on buys
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_swap(
+        &mut self,
+        market: &CategoricalMarket,
+        direction: u8,
+        outcome: usize,
+        sol_amount: u64,
+        token_amount: u64,
+        fee_lamports: u64,
+        side_supply: u64,
+        balance: u64,
+    ) -> Result<()> {
+        let mut swap = PositionSwap {
+            net_sol_in: self.net_sol_in,
+            tokens: self.outcome_tokens[outcome],
+            total_sol_spent: self.total_sol_spent,
+            total_sol_received: self.total_sol_received,
+            fees_paid: self.fees_paid,
+        };
+        swap.apply(
+            market.position_limits(),
+            direction,
+            sol_amount,
+            token_amount,
+            fee_lamports,
+            side_supply,
+            balance,
+        )?;
+
+        self.net_sol_in = swap.net_sol_in;
+        self.total_sol_spent = swap.total_sol_spent;
+        self.total_sol_received = swap.total_sol_received;
+        self.fees_paid = swap.fees_paid;
+        self.outcome_tokens[outcome] = swap.tokens;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::market::tests::test_market;
+
+    const SOL: u64 = 1_000_000_000;
+
+    #[test]
+    fn unlimited_market_accepts_any_buy() {
+        let market = test_market();
+        let mut position = Position::default();
+        position
+            .apply_swap(&market, 0, 1, 1_000 * SOL, 900, 10, 1_000, 900)
+            .unwrap();
+        assert_eq!(position.yes_tokens, 900);
+        assert_eq!(position.net_sol_in, 1_000 * SOL);
+    }
+
+    #[test]
+    fn max_wallet_sol_counts_net_sol_in() {
+        let mut market = test_market();
+        market.max_wallet_sol = 10 * SOL;
+        let mut position = Position::default();
+
+        position
+            .apply_swap(&market, 0, 1, 6 * SOL, 100, 0, 0, 100)
+            .unwrap();
+
+        //  selling frees room for new buys, fees do not count
+        position
+            .apply_swap(&market, 1, 1, 2 * SOL, 50, SOL, 0, 50)
+            .unwrap();
+        assert_eq!(position.net_sol_in, 4 * SOL);
+        position
+            .apply_swap(&market, 0, 0, 6 * SOL, 100, SOL, 0, 100)
+            .unwrap();
+        assert_eq!(position.net_sol_in, 10 * SOL);
+        assert_eq!(
+            position
+                .apply_swap(&market, 0, 0, 1, 1, 0, 0, 101)
+                .unwrap_err(),
+            error!(TakesFunError::PositionLimitExceeded)
+        );
+
+        //  selling for more than was put in never goes below zero
+        let mut position = Position::default();
+        position
+            .apply_swap(&market, 0, 1, 6 * SOL, 100, 0, 0, 100)
+            .unwrap();
+        position
+            .apply_swap(&market, 1, 1, 20 * SOL, 100, 0, 0, 0)
+            .unwrap();
+        assert_eq!(position.net_sol_in, 0);
+    }
+
+    #[test]
+    fn max_wallet_share_is_per_side() {
+        let mut market = test_market();
+        market.max_wallet_share_bps = 1_000;
+        let mut position = Position::default();
+
+        //  10% of the supply of each side
+        position
+            .apply_swap(&market, 0, 1, SOL, 100, 0, 1_000, 100)
+            .unwrap();
+        position
+            .apply_swap(&market, 0, 0, SOL, 100, 0, 1_000, 100)
+            .unwrap();
+
+        //  sells are never limited and free room for new buys
+        position
+            .apply_swap(&market, 1, 1, SOL, 40, 0, 1_000, 60)
+            .unwrap();
+        assert_eq!(position.yes_tokens, 60);
+        position
+            .apply_swap(&market, 0, 1, SOL, 40, 0, 1_000, 100)
+            .unwrap();
+        assert!(position
+            .apply_swap(&market, 0, 1, SOL, 1, 0, 1_000, 101)
+            .is_err());
+    }
+
+    #[test]
+    fn tracks_cost_basis_and_fees() {
+        let market = test_market();
+        let mut position = Position::default();
+
+        position
+            .apply_swap(&market, 0, 1, 10 * SOL, 100, SOL, 0, 100)
+            .unwrap();
+        position
+            .apply_swap(&market, 1, 1, 4 * SOL, 30, SOL / 2, 0, 70)
+            .unwrap();
+
+        assert_eq!(position.total_sol_spent, 11 * SOL);
+        assert_eq!(position.total_sol_received, 4 * SOL - SOL / 2);
+        assert_eq!(position.fees_paid, SOL + SOL / 2);
+        assert_eq!(position.yes_tokens, 70);
+        assert_eq!(position.tokens(0), 0);
+
+        //  tokens received outside the curve are not tracked
+        position
+            .apply_swap(&market, 1, 1, SOL, 500, 0, 0, 0)
+            .unwrap();
+        assert_eq!(position.yes_tokens, 0);
+    }
+
+    #[test]
+    fn transferred_tokens_do_not_reset_the_limits() {
+        let mut market = test_market();
+        market.max_wallet_sol = 10 * SOL;
+        market.max_wallet_share_bps = 1_000;
+        let mut position = Position::default();
+
+        //  at both limits, then 400 tokens arrive from another wallet and are sold
+        position
+            .apply_swap(&market, 0, 1, 10 * SOL, 100, 0, 1_000, 100)
+            .unwrap();
+        position
+            .apply_swap(&market, 1, 1, 40 * SOL, 400, 0, 1_000, 100)
+            .unwrap();
+        assert_eq!(position.net_sol_in, 10 * SOL);
+        assert_eq!(position.yes_tokens, 100);
+        assert_eq!(
+            position
+                .apply_swap(&market, 0, 1, 1, 1, 0, 1_000, 101)
+                .unwrap_err(),
+            error!(TakesFunError::PositionLimitExceeded)
+        );
+
+        //  the share limit counts the balance, not only the tokens bought here
+        market.max_wallet_sol = 0;
+        let mut position = Position::default();
+        position
+            .apply_swap(&market, 0, 1, SOL, 50, 0, 1_000, 50)
+            .unwrap();
+        assert_eq!(
+            position
+                .apply_swap(&market, 0, 1, SOL, 1, 0, 1_000, 111)
+                .unwrap_err(),
+            error!(TakesFunError::PositionLimitExceeded)
+        );
+    }
+}
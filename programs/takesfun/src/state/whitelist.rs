@@ -1,3 +1,6 @@
+use crate::errors::*;
+use crate::state::config::{Config, MAX_FEE_TIERS};
+use crate::utils::{merkle_leaf, verify_merkle_proof};
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 
 #[account]
@@ -6,12 +9,41 @@ pub struct Whitelist {
     pub creator: Pubkey,
     pub first_swap_timestamp: i64,
     pub is_allow: u8,
+    //  fee tier, 0 uses the small fees of the config, see Config::fee_tiers for the others
+    pub tier: u8,
+    //  entry is inactive from this timestamp, 0 means no expiry
+    pub expiry: i64,
+    //  set by remove_wl, proofs can not reactivate the entry until add_wl admits the user again
+    pub revoked: bool,
+    //  tier and expiry were set by add_wl, they take precedence over merkle proofs
+    pub manual: bool,
+}
+
+//  leaf data and proof against Config::whitelist_merkle_root
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WhitelistProof {
+    pub tier: u8,
+    pub expiry: i64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl WhitelistProof {
+    pub fn leaf(&self, user: &Pubkey) -> [u8; 32] {
+        merkle_leaf(&[user.as_ref(), &[self.tier], &self.expiry.to_le_bytes()])
+    }
 }
 
 impl Whitelist {
     pub const SEED_PREFIX: &'static str = "wl-seed";
 
-    pub fn is_whitelister(&mut self, limit_timestamp: i64, time_stamp: i64) -> Result<bool> {
+    //  entries created before a layout change are shorter, the missing fields read as zeroes
+    pub fn try_deserialize_any_layout(data: &[u8]) -> Result<Self> {
+        let mut data = data.to_vec();
+        data.resize(data.len().max(8 + Self::INIT_SPACE), 0);
+        Self::try_deserialize(&mut data.as_slice())
+    }
+
+    pub fn is_whitelister(&self, limit_timestamp: i64, time_stamp: i64) -> Result<bool> {
         let last_time_stamp = self.first_swap_timestamp + limit_timestamp;
 
         if last_time_stamp >= time_stamp {
@@ -20,4 +52,324 @@ impl Whitelist {
             Ok(false)
         }
     }
+
+    //  activate the entry from a merkle proof, no backend co-signing needed
+    pub fn activate_with_proof(
+        &mut self,
+        user: Pubkey,
+        whitelist_proof: &WhitelistProof,
+        global_config: &Config,
+    ) -> Result<()> {
+        require!(
+            global_config.whitelist_merkle_root != [0u8; 32]
+                && verify_merkle_proof(
+                    &whitelist_proof.proof,
+                    &global_config.whitelist_merkle_root,
+                    whitelist_proof.leaf(&user),
+                ),
+            TakesFunError::InvalidWhitelistProof
+        );
+        require!(
+            whitelist_proof.tier as usize <= MAX_FEE_TIERS,
+            TakesFunError::ValueTooLarge
+        );
+        require!(!self.revoked, TakesFunError::WhitelistRevoked);
+
+        //  an active manual entry keeps the tier and expiry the whitelist manager set
+        if self.is_allow == 1 && self.manual {
+            return Ok(());
+        }
+
+        if self.is_allow == 0 {
+            self.creator = user;
+            self.is_allow = 1;
+            self.first_swap_timestamp = 0;
+        }
+        self.tier = whitelist_proof.tier;
+        self.expiry = whitelist_proof.expiry;
+        Ok(())
+    }
+
+    //  manual entries override proofs and lift a previous revocation
+    pub fn add(&mut self, user: Pubkey, tier: u8, expiry: i64) -> Result<()> {
+        require!(tier as usize <= MAX_FEE_TIERS, TakesFunError::ValueTooLarge);
+
+        if self.is_allow == 0 {
+            self.creator = user;
+            self.is_allow = 1;
+            self.first_swap_timestamp = 0;
+        }
+        //  existing entries can be moved to another tier or expiry
+        self.tier = tier;
+        self.expiry = expiry;
+        self.revoked = false;
+        self.manual = true;
+        Ok(())
+    }
+
+    pub fn revoke(&mut self) {
+        self.is_allow = 0;
+        self.tier = 0;
+        self.expiry = 0;
+        self.revoked = true;
+        self.manual = false;
+    }
+
+    pub fn is_active(&self, time_stamp: i64) -> bool {
+        self.is_allow == 1 && (self.expiry == 0 || time_stamp < self.expiry)
+    }
+
+    //  fee tier applying to a swap at time_stamp, None means standard fees
+    pub fn fee_tier(&self, global_config: &Config, time_stamp: i64) -> Result<Option<u8>> {
+        if !self.is_active(time_stamp) {
+            return Ok(None);
+        }
+
+        let limit_timestamp = match self.tier {
+            0 => global_config.limit_timestamp,
+            tier => match global_config.fee_tiers.get(tier as usize - 1) {
+                //  tiers without a window apply until the entry expires
+                Some(fee_tier) if fee_tier.limit_timestamp == 0 => return Ok(Some(tier)),
+                Some(fee_tier) => fee_tier.limit_timestamp,
+                None => return Ok(None),
+            },
+        };
+
+        if self.is_whitelister(limit_timestamp, time_stamp)? {
+            Ok(Some(self.tier))
+        } else {
+            Ok(None)
+        }
+    }
+
+    //  fee tier and launch phase access of a swapping user, activates the entry from
+    //  whitelist_proof first, users without an entry pay standard fees
+    pub fn swap_access(
+        whitelist: Option<&mut Self>,
+        user: Pubkey,
+        whitelist_proof: Option<&WhitelistProof>,
+        global_config: &Config,
+        time_stamp: i64,
+    ) -> Result<(Option<u8>, bool)> {
+        let Some(whitelist) = whitelist else {
+            require!(
+                whitelist_proof.is_none(),
+                TakesFunError::MissingWhitelistAccount
+            );
+            return Ok((None, false));
+        };
+
+        if let Some(whitelist_proof) = whitelist_proof {
+            whitelist.activate_with_proof(user, whitelist_proof, global_config)?;
+        }
+        if whitelist.is_allow == 1 && whitelist.first_swap_timestamp == 0 {
+            whitelist.first_swap_timestamp = time_stamp;
+        }
+
+        Ok((
+            whitelist.fee_tier(global_config, time_stamp)?,
+            whitelist.is_active(time_stamp),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::config::tests::test_config;
+    use anchor_lang::{solana_program::hash::hashv, Discriminator};
+
+    //  whitelist layout before tiers and expiry
+    #[derive(AnchorSerialize)]
+    struct LegacyWhitelist {
+        creator: Pubkey,
+        first_swap_timestamp: i64,
+        is_allow: u8,
+    }
+
+    #[test]
+    fn legacy_whitelist_upgrades_after_realloc() {
+        let legacy = LegacyWhitelist {
+            creator: Pubkey::new_unique(),
+            first_swap_timestamp: 1_700_000_000,
+            is_allow: 1,
+        };
+        let mut data = Whitelist::DISCRIMINATOR.to_vec();
+        data.extend(legacy.try_to_vec().unwrap());
+        assert!(Whitelist::try_deserialize(&mut data.as_slice()).is_err());
+
+        let whitelist = Whitelist::try_deserialize_any_layout(&data).unwrap();
+        assert_eq!(whitelist.creator, legacy.creator);
+        assert_eq!(whitelist.first_swap_timestamp, legacy.first_swap_timestamp);
+        assert_eq!(whitelist.tier, 0);
+        assert!(whitelist.is_active(i64::MAX));
+    }
+
+    #[test]
+    fn any_layout_checks_discriminator() {
+        let mut data = Whitelist::DISCRIMINATOR.to_vec();
+        data.extend(Whitelist::default().try_to_vec().unwrap());
+        assert!(Whitelist::try_deserialize_any_layout(&data).is_ok());
+
+        data[0] ^= 1;
+        assert!(Whitelist::try_deserialize_any_layout(&data).is_err());
+    }
+
+    fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&[1u8], a, b]).to_bytes()
+        } else {
+            hashv(&[&[1u8], b, a]).to_bytes()
+        }
+    }
+
+    //  root and proofs of a 4 leaf tree
+    fn tree(leaves: &[[u8; 32]; 4]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+        let left = hash_pair(&leaves[0], &leaves[1]);
+        let right = hash_pair(&leaves[2], &leaves[3]);
+        let proofs = vec![
+            vec![leaves[1], right],
+            vec![leaves[0], right],
+            vec![leaves[3], left],
+            vec![leaves[2], left],
+        ];
+        (hash_pair(&left, &right), proofs)
+    }
+
+    struct Setup {
+        config: Config,
+        users: Vec<Pubkey>,
+        proofs: Vec<WhitelistProof>,
+    }
+
+    fn setup() -> Setup {
+        let users: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut proofs: Vec<WhitelistProof> = (0..4)
+            .map(|i| WhitelistProof {
+                tier: i as u8 % 3 + 1,
+                expiry: 1_800_000_000 + i as i64,
+                proof: vec![],
+            })
+            .collect();
+        let leaves: [[u8; 32]; 4] = core::array::from_fn(|i| proofs[i].leaf(&users[i]));
+        let (root, paths) = tree(&leaves);
+        for (proof, path) in proofs.iter_mut().zip(paths) {
+            proof.proof = path;
+        }
+
+        let mut config = test_config();
+        config.whitelist_merkle_root = root;
+        Setup {
+            config,
+            users,
+            proofs,
+        }
+    }
+
+    #[test]
+    fn merkle_proof_verifies_every_leaf() {
+        let setup = setup();
+        for (user, proof) in setup.users.iter().zip(setup.proofs.iter()) {
+            assert!(verify_merkle_proof(
+                &proof.proof,
+                &setup.config.whitelist_merkle_root,
+                proof.leaf(user),
+            ));
+        }
+    }
+
+    #[test]
+    fn merkle_proof_rejects_other_data() {
+        let setup = setup();
+        let user = setup.users[0];
+        let mut whitelist = Whitelist::default();
+
+        //  proof of another user
+        assert!(whitelist
+            .activate_with_proof(setup.users[1], &setup.proofs[0], &setup.config)
+            .is_err());
+
+        //  tampered tier or expiry
+        let mut proof = setup.proofs[0].clone();
+        proof.tier += 1;
+        assert!(whitelist
+            .activate_with_proof(user, &proof, &setup.config)
+            .is_err());
+        let mut proof = setup.proofs[0].clone();
+        proof.expiry += 1;
+        assert!(whitelist
+            .activate_with_proof(user, &proof, &setup.config)
+            .is_err());
+
+        //  truncated path
+        let mut proof = setup.proofs[0].clone();
+        proof.proof.pop();
+        assert!(whitelist
+            .activate_with_proof(user, &proof, &setup.config)
+            .is_err());
+
+        //  no root configured
+        let mut config = test_config();
+        config.whitelist_merkle_root = [0u8; 32];
+        assert!(whitelist
+            .activate_with_proof(user, &setup.proofs[0], &config)
+            .is_err());
+        assert_eq!(whitelist.is_allow, 0);
+    }
+
+    #[test]
+    fn merkle_proof_activates_entry() {
+        let setup = setup();
+        let mut whitelist = Whitelist::default();
+        whitelist
+            .activate_with_proof(setup.users[2], &setup.proofs[2], &setup.config)
+            .unwrap();
+
+        assert_eq!(whitelist.creator, setup.users[2]);
+        assert_eq!(whitelist.tier, setup.proofs[2].tier);
+        assert_eq!(whitelist.expiry, setup.proofs[2].expiry);
+        assert!(whitelist.is_active(setup.proofs[2].expiry - 1));
+        assert!(!whitelist.is_active(setup.proofs[2].expiry));
+    }
+
+    #[test]
+    fn revoked_entry_can_not_be_reactivated_with_proof() {
+        let setup = setup();
+        let mut whitelist = Whitelist::default();
+        whitelist
+            .activate_with_proof(setup.users[0], &setup.proofs[0], &setup.config)
+            .unwrap();
+        whitelist.revoke();
+
+        assert!(whitelist
+            .activate_with_proof(setup.users[0], &setup.proofs[0], &setup.config)
+            .is_err());
+        assert!(!whitelist.is_active(0));
+
+        //  the whitelist manager can admit the user again
+        whitelist.add(setup.users[0], 0, 0).unwrap();
+        assert!(whitelist.is_active(0));
+        assert!(!whitelist.revoked);
+    }
+
+    #[test]
+    fn manual_tier_takes_precedence_over_proof() {
+        let setup = setup();
+        let mut whitelist = Whitelist::default();
+        whitelist.add(setup.users[0], 3, 0).unwrap();
+        whitelist
+            .activate_with_proof(setup.users[0], &setup.proofs[0], &setup.config)
+            .unwrap();
+        assert_eq!(whitelist.tier, 3);
+        assert_eq!(whitelist.expiry, 0);
+
+        //  proofs still update entries they created
+        let mut whitelist = Whitelist::default();
+        whitelist
+            .activate_with_proof(setup.users[0], &setup.proofs[0], &setup.config)
+            .unwrap();
+        whitelist.add(setup.users[0], 2, 0).unwrap();
+        assert_eq!(whitelist.tier, 2);
+        assert!(whitelist.manual);
+    }
 }
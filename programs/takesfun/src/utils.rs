@@ -1,8 +1,30 @@
+use crate::constants::{GLOBAL, MARKET_VAULT};
+use crate::errors::TakesFunError;
+use crate::state::{
+    market::{InitialBuy, Market, MarketAccount},
+    position::Position,
+    whitelist::Whitelist,
+};
 use crate::*;
-use anchor_lang::solana_program::hash::hash;
-use anchor_spl::token::{self, Token};
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token,
+    token_2022::{
+        self,
+        spl_token_2022::{
+            extension::{
+                non_transferable::NonTransferable, BaseStateWithExtensions, ExtensionType,
+                StateWithExtensions,
+            },
+            state::{Account as TokenAccountState, Mint as MintState},
+        },
+    },
+    token_interface::{self, spl_token_2022::instruction::AuthorityType, TokenInterface},
+};
 use solana_program::program::{invoke, invoke_signed};
 use std::ops::{Div, Mul};
+use unicode_normalization::UnicodeNormalization;
 
 pub fn convert_to_float(value: u64, decimals: u8) -> f64 {
     (value as f64).div(f64::powf(10.0, decimals as f64))
@@ -48,50 +70,709 @@ pub fn sol_transfer_with_signer<'info>(
     Ok(())
 }
 
+//  close an account owned by this program without deserializing it, same as `close = `
+pub fn close_program_account<'info>(
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(TakesFunError::ArithmeticError)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
 //  transfer token from user
 pub fn token_transfer_user<'info>(
     from: AccountInfo<'info>,
     authority: &Signer<'info>,
     to: AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: &Interface<'info, TokenInterface>,
     amount: u64,
 ) -> Result<()> {
     let cpi_ctx: CpiContext<_> = CpiContext::new(
         token_program.to_account_info(),
-        token::Transfer {
+        token_interface::TransferChecked {
             from,
+            mint,
             authority: authority.to_account_info(),
             to,
         },
     );
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, decimals)?;
 
     Ok(())
 }
 
 //  transfer token from PDA
+#[allow(clippy::too_many_arguments)]
 pub fn token_transfer_with_signer<'info>(
     from: AccountInfo<'info>,
     authority: AccountInfo<'info>,
     to: AccountInfo<'info>,
-    token_program: &Program<'info, Token>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: &Interface<'info, TokenInterface>,
     signer_seeds: &[&[&[u8]]],
     amount: u64,
 ) -> Result<()> {
     let cpi_ctx: CpiContext<_> = CpiContext::new_with_signer(
         token_program.to_account_info(),
-        token::Transfer {
+        token_interface::TransferChecked {
             from,
+            mint,
+            to,
+            authority,
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, decimals)?;
+
+    Ok(())
+}
+
+//  mint token from PDA, soulbound outcome tokens are minted on buys
+pub fn token_mint_with_signer<'info>(
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    let cpi_ctx: CpiContext<_> = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token_interface::MintTo {
+            mint,
             to,
             authority,
         },
         signer_seeds,
     );
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::mint_to(cpi_ctx, amount)?;
 
     Ok(())
 }
 
+//  burn token from user, soulbound outcome tokens are burned on sells
+pub fn token_burn_user<'info>(
+    from: AccountInfo<'info>,
+    authority: &Signer<'info>,
+    mint: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_ctx: CpiContext<_> = CpiContext::new(
+        token_program.to_account_info(),
+        token_interface::Burn {
+            mint,
+            from,
+            authority: authority.to_account_info(),
+        },
+    );
+    token_interface::burn(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+//  spl collateral of a market, the global vault owns vault_ata
+pub struct CollateralToken<'info> {
+    pub mint: AccountInfo<'info>,
+    pub decimals: u8,
+    pub token_program: AccountInfo<'info>,
+    pub vault_ata: AccountInfo<'info>,
+    pub user_ata: AccountInfo<'info>,
+}
+
+//  a pda holding collateral, signer_seeds sign for it
+pub struct Vault<'a, 'info> {
+    pub account: AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+//  moves the collateral of a market, native sol sits in the market vault and moves through the
+//  system program, spl mints sit in the global vault ata and move through token cpis between
+//  the collateral atas of each party
+pub enum Collateral<'a, 'info> {
+    Sol {
+        vault: Vault<'a, 'info>,
+        system_program: &'a Program<'info, System>,
+    },
+    Token {
+        vault: Vault<'a, 'info>,
+        token: CollateralToken<'info>,
+    },
+}
+
+impl<'a, 'info> Collateral<'a, 'info> {
+    //  markets with a default collateral mint trade native sol
+    pub fn new(
+        collateral_mint: Pubkey,
+        market_vault: Vault<'a, 'info>,
+        global_vault: Vault<'a, 'info>,
+        system_program: &'a Program<'info, System>,
+        token: Option<CollateralToken<'info>>,
+    ) -> Result<Self> {
+        if collateral_mint == Pubkey::default() {
+            return Ok(Collateral::Sol {
+                vault: market_vault,
+                system_program,
+            });
+        }
+        let token = token.ok_or(TakesFunError::MissingCollateralAccount)?;
+        require_keys_eq!(
+            token.mint.key(),
+            collateral_mint,
+            TakesFunError::IncorrectCollateralMint
+        );
+        Ok(Collateral::Token {
+            vault: global_vault,
+            token,
+        })
+    }
+
+    //  account receiving collateral for a party, its collateral ata on spl markets
+    pub fn account(
+        &self,
+        wallet: &AccountInfo<'info>,
+        ata: Option<AccountInfo<'info>>,
+    ) -> Result<AccountInfo<'info>> {
+        match self {
+            Collateral::Sol { .. } => Ok(wallet.clone()),
+            Collateral::Token { .. } => Ok(ata.ok_or(TakesFunError::MissingCollateralAccount)?),
+        }
+    }
+
+    pub fn transfer_to_vault(&self, user: &Signer<'info>, amount: u64) -> Result<()> {
+        let to = match self {
+            Collateral::Sol { vault, .. } => vault.account.clone(),
+            Collateral::Token { token, .. } => token.vault_ata.clone(),
+        };
+        self.transfer_from_user(user, to, amount)
+    }
+
+    pub fn transfer_from_user(
+        &self,
+        user: &Signer<'info>,
+        to: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        match self {
+            Collateral::Sol { system_program, .. } => {
+                sol_transfer_from_user(user, to, system_program, amount)
+            }
+            Collateral::Token { token, .. } => collateral_transfer(
+                token,
+                token.user_ata.clone(),
+                user.to_account_info(),
+                to,
+                &[],
+                amount,
+            ),
+        }
+    }
+
+    pub fn transfer_from_vault(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        match self {
+            Collateral::Sol {
+                vault,
+                system_program,
+            } => sol_transfer_with_signer(
+                vault.account.clone(),
+                to,
+                system_program,
+                vault.signer_seeds,
+                amount,
+            ),
+            Collateral::Token { vault, token } => collateral_transfer(
+                token,
+                token.vault_ata.clone(),
+                vault.account.clone(),
+                to,
+                vault.signer_seeds,
+                amount,
+            ),
+        }
+    }
+
+    pub fn transfer_to_user(&self, user: &Signer<'info>, amount: u64) -> Result<()> {
+        let to = match self {
+            Collateral::Sol { .. } => user.to_account_info(),
+            Collateral::Token { token, .. } => token.user_ata.clone(),
+        };
+        self.transfer_from_vault(to, amount)
+    }
+}
+
+fn collateral_transfer<'info>(
+    token: &CollateralToken<'info>,
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token.token_program.clone(),
+            token_interface::TransferChecked {
+                from,
+                mint: token.mint.clone(),
+                to,
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        token.decimals,
+    )
+}
+
+//  accounts of the amm initialize_pool instruction, in instruction order
+pub struct AmmInitializePool<'info> {
+    pub pool: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub pool_token_account: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub liquidity_provider: AccountInfo<'info>,
+    pub provider_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+//  seed a constant product pool, the amm takes token_amount tokens and sol_amount lamports
+//  from the liquidity provider, which signs with signer_seeds
+pub fn amm_initialize_pool<'info>(
+    amm_program: AccountInfo<'info>,
+    accounts: AmmInitializePool<'info>,
+    signer_seeds: &[&[&[u8]]],
+    token_amount: u64,
+    sol_amount: u64,
+) -> Result<()> {
+    let mut data = hash(b"global:initialize_pool").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&token_amount.to_le_bytes());
+    data.extend_from_slice(&sol_amount.to_le_bytes());
+
+    let ix = solana_program::instruction::Instruction {
+        program_id: amm_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.pool.key(), false),
+            AccountMeta::new_readonly(accounts.mint.key(), false),
+            AccountMeta::new(accounts.pool_token_account.key(), false),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new(accounts.liquidity_provider.key(), true),
+            AccountMeta::new(accounts.provider_token_account.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+            AccountMeta::new_readonly(accounts.associated_token_program.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            accounts.pool,
+            accounts.mint,
+            accounts.pool_token_account,
+            accounts.payer,
+            accounts.liquidity_provider,
+            accounts.provider_token_account,
+            accounts.token_program,
+            accounts.associated_token_program,
+            accounts.system_program,
+            amm_program,
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+//  moves the share of the global vault holdings a legacy market is owed into its market
+//  vault, see Config::take_legacy_share, returns the lamports moved
+pub fn move_legacy_sol<'info>(
+    global_config: &mut Config,
+    payer: &Signer<'info>,
+    global_vault: AccountInfo<'info>,
+    market_vault: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    global_vault_bump: u8,
+    sol_liabilities: u64,
+) -> Result<u64> {
+    let rent = Rent::get()?;
+    let holdings = global_vault
+        .lamports()
+        .saturating_sub(rent.minimum_balance(global_vault.data_len()));
+    let amount = global_config.take_legacy_share(sol_liabilities, holdings)?;
+
+    if market_vault.lamports() == 0 {
+        sol_transfer_from_user(
+            payer,
+            market_vault.clone(),
+            system_program,
+            rent.minimum_balance(0),
+        )?;
+    }
+
+    if amount > 0 {
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+        sol_transfer_with_signer(
+            global_vault,
+            market_vault,
+            system_program,
+            signer_seeds,
+            amount,
+        )?;
+    }
+    Ok(amount)
+}
+
+//  tlv header plus the borsh encoded token metadata without its strings:
+//  update authority, mint, three string length prefixes and an empty additional metadata vec
+const TOKEN_METADATA_BASE_LEN: usize = 4 + 32 + 32 + 4 + 4 + 4 + 4;
+
+//  accounts used to create an outcome mint
+pub struct CreateOutcomeMint<'info> {
+    pub mint: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub global_vault: AccountInfo<'info>,
+    pub global_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+//  create a token-2022 outcome mint with the global vault as authority and its metadata on the
+//  mint through the metadata pointer extension. the supply is minted into the global vault ata
+//  and the mint authority revoked, soulbound mints carry the non transferable extension instead
+//  and keep the global vault as mint authority, the curve mints and burns them on every trade
+#[allow(clippy::too_many_arguments)]
+pub fn create_outcome_mint<'info>(
+    accounts: CreateOutcomeMint<'info>,
+    signer_seeds: &[&[&[u8]]],
+    decimals: u8,
+    supply: u64,
+    soulbound: bool,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    require!(
+        accounts.token_program.key() == token_2022::ID,
+        TakesFunError::UnsupportedTokenProgram
+    );
+    let rent = Rent::get()?;
+
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if soulbound {
+        extensions.push(ExtensionType::NonTransferable);
+    }
+    let space = token_interface::find_mint_account_size(Some(&extensions))?;
+    system_program::create_account(
+        CpiContext::new(
+            accounts.system_program.clone(),
+            system_program::CreateAccount {
+                from: accounts.payer.clone(),
+                to: accounts.mint.clone(),
+            },
+        ),
+        rent.minimum_balance(space),
+        space as u64,
+        accounts.token_program.key,
+    )?;
+
+    //  the metadata lives on the mint itself
+    token_interface::metadata_pointer_initialize(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            token_interface::MetadataPointerInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ),
+        Some(accounts.global_vault.key()),
+        Some(accounts.mint.key()),
+    )?;
+
+    if soulbound {
+        token_interface::non_transferable_mint_initialize(CpiContext::new(
+            accounts.token_program.clone(),
+            token_interface::NonTransferableMintInitialize {
+                token_program_id: accounts.token_program.clone(),
+                mint: accounts.mint.clone(),
+            },
+        ))?;
+    }
+
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            accounts.token_program.clone(),
+            token_interface::InitializeMint2 {
+                mint: accounts.mint.clone(),
+            },
+        ),
+        decimals,
+        accounts.global_vault.key,
+        None,
+    )?;
+
+    //  the token program reallocs the mint for the metadata, fund the new size up front
+    let metadata_len = TOKEN_METADATA_BASE_LEN + name.len() + symbol.len() + uri.len();
+    let lamports = rent
+        .minimum_balance(space + metadata_len)
+        .saturating_sub(accounts.mint.lamports());
+    if lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.clone(),
+                system_program::Transfer {
+                    from: accounts.payer.clone(),
+                    to: accounts.mint.clone(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            token_interface::TokenMetadataInitialize {
+                token_program_id: accounts.token_program.clone(),
+                metadata: accounts.mint.clone(),
+                update_authority: accounts.global_vault.clone(),
+                mint_authority: accounts.global_vault.clone(),
+                mint: accounts.mint.clone(),
+            },
+            signer_seeds,
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+
+    associated_token::create(CpiContext::new(
+        accounts.associated_token_program.clone(),
+        associated_token::Create {
+            payer: accounts.payer.clone(),
+            associated_token: accounts.global_token_account.clone(),
+            authority: accounts.global_vault.clone(),
+            mint: accounts.mint.clone(),
+            token_program: accounts.token_program.clone(),
+            system_program: accounts.system_program.clone(),
+        },
+    ))?;
+
+    if soulbound {
+        return Ok(());
+    }
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            token_interface::MintTo {
+                mint: accounts.mint.clone(),
+                to: accounts.global_token_account.clone(),
+                authority: accounts.global_vault.clone(),
+            },
+            signer_seeds,
+        ),
+        supply,
+    )?;
+
+    //  revoke mint authority
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            token_interface::SetAuthority {
+                current_authority: accounts.global_vault.clone(),
+                account_or_mint: accounts.mint,
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    Ok(())
+}
+
+//  decimals and supply of a token or token-2022 mint
+pub fn mint_state(mint: &AccountInfo) -> Result<(u8, u64)> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok((state.base.decimals, state.base.supply))
+}
+
+//  soulbound outcome mints carry the token-2022 non transferable extension
+pub fn is_soulbound(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != token_2022::ID {
+        return Ok(false);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(state.get_extension::<NonTransferable>().is_ok())
+}
+
+//  token balance of a token or token-2022 account
+pub fn token_balance(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    Ok(state.base.amount)
+}
+
+//  accounts of the buy executed with a market launch, see execute_initial_buy
+pub struct InitialBuyAccounts<'a, 'info> {
+    pub global_config: &'a Account<'info, Config>,
+    pub market: &'a mut Account<'info, Market>,
+    //  pays for the buy and the created accounts
+    pub payer: &'a Signer<'info>,
+    //  receives the tokens and owns the position
+    pub buyer: AccountInfo<'info>,
+    //  the yes mint is created in the same instruction, the swap reads it from its data
+    pub yes_token: AccountInfo<'info>,
+    pub no_token: AccountInfo<'info>,
+    pub global_yes_ata: AccountInfo<'info>,
+    pub global_no_ata: AccountInfo<'info>,
+    pub buyer_yes_ata: Option<AccountInfo<'info>>,
+    pub buyer_no_ata: Option<AccountInfo<'info>>,
+    pub global_vault: AccountInfo<'info>,
+    pub market_vault: AccountInfo<'info>,
+    pub creator_vault: AccountInfo<'info>,
+    pub team_wallet: Option<AccountInfo<'info>>,
+    pub team_wallet2: Option<AccountInfo<'info>>,
+    pub team_wallet_collateral_ata: Option<AccountInfo<'info>>,
+    pub team_wallet2_collateral_ata: Option<AccountInfo<'info>>,
+    pub creator_vault_collateral_ata: Option<AccountInfo<'info>>,
+    pub collateral_token: Option<CollateralToken<'info>>,
+    pub position: Option<&'a mut Account<'info, Position>>,
+    //  whitelist entry of the buyer, gives the buy its fee tier and launch phase access
+    pub whitelist: Option<&'a mut Whitelist>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+//  buy executed with the launch, before anyone else can trade. it goes through the same fee
+//  tier and launch phase checks as a swap of the buyer
+pub fn execute_initial_buy<'info>(
+    accounts: InitialBuyAccounts<'_, 'info>,
+    initial_buy: InitialBuy,
+    global_vault_bump: u8,
+    market_vault_bump: u8,
+) -> Result<()> {
+    let mut buyer_yes_ata = accounts
+        .buyer_yes_ata
+        .ok_or(TakesFunError::MissingInitialBuyAccount)?;
+    let mut buyer_no_ata = accounts
+        .buyer_no_ata
+        .ok_or(TakesFunError::MissingInitialBuyAccount)?;
+    let mut team_wallet = accounts
+        .team_wallet
+        .ok_or(TakesFunError::MissingInitialBuyAccount)?;
+    let mut team_wallet2 = accounts
+        .team_wallet2
+        .ok_or(TakesFunError::MissingInitialBuyAccount)?;
+
+    let (buyer_ata, mint) = match initial_buy.token_type {
+        0 => (&buyer_no_ata, accounts.no_token.clone()),
+        _ => (&buyer_yes_ata, accounts.yes_token.clone()),
+    };
+    if buyer_ata.data_is_empty() {
+        associated_token::create(CpiContext::new(
+            accounts.associated_token_program.clone(),
+            associated_token::Create {
+                payer: accounts.payer.to_account_info(),
+                associated_token: buyer_ata.clone(),
+                authority: accounts.buyer.clone(),
+                mint,
+                token_program: accounts.token_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+            },
+        ))?;
+    }
+
+    let market_key = accounts.market.key();
+    let market_vault_seeds: &[&[&[u8]]] = &[&[
+        MARKET_VAULT.as_bytes(),
+        market_key.as_ref(),
+        &[market_vault_bump],
+    ]];
+    let global_vault_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+    let collateral = Collateral::new(
+        accounts.market.collateral_mint,
+        Vault {
+            account: accounts.market_vault,
+            signer_seeds: market_vault_seeds,
+        },
+        Vault {
+            account: accounts.global_vault.clone(),
+            signer_seeds: global_vault_seeds,
+        },
+        accounts.system_program,
+        accounts.collateral_token,
+    )?;
+    team_wallet = collateral.account(&team_wallet, accounts.team_wallet_collateral_ata)?;
+    team_wallet2 = collateral.account(&team_wallet2, accounts.team_wallet2_collateral_ata)?;
+    let mut creator_vault = collateral.account(
+        &accounts.creator_vault,
+        accounts.creator_vault_collateral_ata,
+    )?;
+
+    //  the position belongs to the buyer even when someone else pays
+    let mut position = accounts.position;
+    if let Some(position) = position.as_deref_mut() {
+        if position.user == Pubkey::default() {
+            position.market = market_key;
+            position.user = accounts.buyer.key();
+        }
+    }
+
+    let (fee_tier, is_whitelisted) = Whitelist::swap_access(
+        accounts.whitelist,
+        accounts.buyer.key(),
+        None,
+        accounts.global_config,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let mut global_yes_ata = accounts.global_yes_ata;
+    let mut global_no_ata = accounts.global_no_ata;
+    let mut source = accounts.global_vault;
+    accounts.market.swap(
+        accounts.global_config,
+        &accounts.yes_token,
+        &mut global_yes_ata,
+        &mut buyer_yes_ata,
+        &accounts.no_token,
+        &mut global_no_ata,
+        &mut buyer_no_ata,
+        &mut source,
+        &mut team_wallet,
+        &mut team_wallet2,
+        &mut creator_vault,
+        initial_buy.amount,
+        0,
+        initial_buy.token_type,
+        initial_buy.minimum_receive_amount,
+        accounts.payer,
+        global_vault_seeds,
+        fee_tier,
+        is_whitelisted,
+        position,
+        accounts.token_program,
+        &collateral,
+    )
+}
+
 pub fn bps_mul(bps: u64, value: u64, divisor: u64) -> Option<u64> {
     bps_mul_raw(bps, value, divisor).unwrap().try_into().ok()
 }
@@ -102,6 +783,20 @@ pub fn bps_mul_raw(bps: u64, value: u64, divisor: u64) -> Option<u128> {
         .checked_div(divisor as u128)
 }
 
+//  value * numerator / denominator rounded down, None on a zero denominator or a result past u64
+pub fn mul_div(value: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    (value as u128)
+        .checked_mul(numerator as u128)?
+        .checked_div(denominator as u128)?
+        .try_into()
+        .ok()
+}
+
+//  canonical form of a market question, equal questions always hash to the same pda
+pub fn normalize_market_info(market_info: &str) -> String {
+    market_info.trim().nfc().collect()
+}
+
 pub trait StringExt {
     fn to_hashed_bytes(&self) -> [u8; 32];
 }
@@ -112,3 +807,21 @@ impl StringExt for String {
         hash.to_bytes()
     }
 }
+
+//  leaves and nodes are domain separated, node children are hashed in sorted order
+pub fn merkle_leaf(data: &[&[u8]]) -> [u8; 32] {
+    let mut parts: Vec<&[u8]> = vec![&[0u8]];
+    parts.extend_from_slice(data);
+    hashv(&parts).to_bytes()
+}
+
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&[1u8], &node, sibling]).to_bytes()
+        } else {
+            hashv(&[&[1u8], sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
@@ -0,0 +1,82 @@
+//  market creation seeded by the question with a creator buy, run against a local validator:
+//  anchor test --provider.cluster localnet
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { expect } from "chai";
+
+import { Takesfun } from "../target/types/takesfun";
+import { MockAmm } from "../target/types/mock_amm";
+import { createMarketSecondTx, mintNoTokenTx } from "../lib/scripts";
+import { ensureTestConfig } from "../lib/test-config";
+import { SEED_CONFIG, SEED_POSITION, TEST_NO_SYMBOL, TEST_NO_URI, TEST_YES_SYMBOL, TEST_YES_URI } from "../lib/constant";
+
+describe("create_market_second", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Takesfun as Program<Takesfun>;
+  const amm = anchor.workspace.MockAmm as Program<MockAmm>;
+  const connection = provider.connection;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+  const tokenProgram = TOKEN_2022_PROGRAM_ID;
+
+  before(async () => {
+    await ensureTestConfig(provider, program, amm.programId);
+  });
+
+  it("buys for a creator other than the payer", async () => {
+    const config = await program.account.config.fetch(
+      PublicKey.findProgramAddressSync([Buffer.from(SEED_CONFIG)], program.programId)[0]
+    );
+    //  the creator does not sign, its accounts are derived from the creator_wallet argument
+    const creator = Keypair.generate().publicKey;
+    const amount = new BN(100_000_000);
+
+    const noTx = await mintNoTokenTx(TEST_NO_SYMBOL, TEST_NO_URI, admin.publicKey, connection, program, tokenProgram);
+    const yesTx = await createMarketSecondTx(
+      TEST_YES_SYMBOL,
+      TEST_YES_URI,
+      "Will the creator buy at launch?",
+      admin.publicKey,
+      admin.publicKey,
+      creator,
+      config.teamWallet,
+      config.teamWallet2,
+      noTx.no_tokenKp.publicKey,
+      { tokenType: 1, amount, minimumReceiveAmount: new BN(0) },
+      connection,
+      program,
+      tokenProgram
+    );
+    const tx = new Transaction().add(...noTx.tx.instructions, ...yesTx.tx.instructions);
+    tx.feePayer = admin.publicKey;
+    tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+    await provider.sendAndConfirm(tx, [noTx.no_tokenKp, yesTx.yes_tokenKp]);
+
+    const market = await program.account.market.fetch(yesTx.market);
+    expect(market.creator.equals(creator)).to.be.true;
+    expect(market.payer.equals(admin.publicKey)).to.be.true;
+    expect(market.realYesSolReserves.gtn(0)).to.be.true;
+    expect(market.realNoSolReserves.isZero()).to.be.true;
+
+    const creatorYes = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(yesTx.yes_tokenKp.publicKey, creator, true, tokenProgram),
+      undefined,
+      tokenProgram
+    );
+    expect(creatorYes.amount > BigInt(0)).to.be.true;
+
+    const position = await program.account.position.fetch(
+      PublicKey.findProgramAddressSync(
+        [Buffer.from(SEED_POSITION), yesTx.market.toBytes(), creator.toBytes()],
+        program.programId
+      )[0]
+    );
+    expect(position.user.equals(creator)).to.be.true;
+    expect(position.market.equals(yesTx.market)).to.be.true;
+    expect(position.yesTokens.toString()).to.equal(creatorYes.amount.toString());
+  });
+});
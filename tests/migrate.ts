@@ -0,0 +1,181 @@
+//  migration of a graduated market into the mock amm, run against a local validator:
+//  anchor test --provider.cluster localnet
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { expect } from "chai";
+
+import { Takesfun } from "../target/types/takesfun";
+import { MockAmm } from "../target/types/mock_amm";
+import { createMarketTx, mintNoTokenTx, swapTx } from "../lib/scripts";
+import { GRADUATION_THRESHOLD, ensureTestConfig } from "../lib/test-config";
+import {
+  MARKET_INFO,
+  SEED_CONFIG,
+  SEED_MARKET,
+  TEST_NO_SYMBOL,
+  TEST_NO_URI,
+  TEST_YES_SYMBOL,
+  TEST_YES_URI,
+} from "../lib/constant";
+
+const SEED_POOL = "pool";
+
+describe("migrate", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Takesfun as Program<Takesfun>;
+  const amm = anchor.workspace.MockAmm as Program<MockAmm>;
+  const connection = provider.connection;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+  const tokenProgram = TOKEN_2022_PROGRAM_ID;
+
+  let yesToken: PublicKey;
+  let noToken: PublicKey;
+  let market: PublicKey;
+
+  const send = async (tx: Transaction, ...signers: Keypair[]) => {
+    tx.feePayer = admin.publicKey;
+    tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+    return provider.sendAndConfirm(tx, signers);
+  };
+
+  const swap = async (amount: number, direction: number, tokenType: number) =>
+    send(await swapTx(admin.publicKey, yesToken, noToken, amount, direction, tokenType, connection, program, tokenProgram));
+
+  const pool = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from(SEED_POOL), mint.toBytes()], amm.programId)[0];
+
+  const migrate = (yesPool: PublicKey, noPool: PublicKey, accounts = { market, yesToken, noToken }) =>
+    program.methods
+      .migrate()
+      .accounts({
+        migrationAuthority: admin.publicKey,
+        ...accounts,
+        yesPool,
+        yesPoolTokenAccount: getAssociatedTokenAddressSync(accounts.yesToken, yesPool, true, tokenProgram),
+        noPool,
+        noPoolTokenAccount: getAssociatedTokenAddressSync(accounts.noToken, noPool, true, tokenProgram),
+        ammProgram: amm.programId,
+        tokenProgram,
+      } as any)
+      .rpc();
+
+  const createMarket = async (soulbound: boolean) => {
+    const config = await program.account.config.fetch(
+      PublicKey.findProgramAddressSync([Buffer.from(SEED_CONFIG)], program.programId)[0]
+    );
+    const noTx = await mintNoTokenTx(TEST_NO_SYMBOL, TEST_NO_URI, admin.publicKey, connection, program, tokenProgram, soulbound);
+    const yesTx = await createMarketTx(
+      TEST_YES_SYMBOL,
+      TEST_YES_URI,
+      MARKET_INFO,
+      admin.publicKey,
+      config.teamWallet,
+      config.teamWallet2,
+      noTx.no_tokenKp.publicKey,
+      connection,
+      program,
+      tokenProgram,
+      soulbound
+    );
+    await send(
+      new Transaction().add(...noTx.tx.instructions, ...yesTx.tx.instructions),
+      noTx.no_tokenKp,
+      yesTx.yes_tokenKp
+    );
+
+    const yes = yesTx.yes_tokenKp.publicKey;
+    const no = noTx.no_tokenKp.publicKey;
+    return {
+      yesToken: yes,
+      noToken: no,
+      market: PublicKey.findProgramAddressSync(
+        [Buffer.from(SEED_MARKET), yes.toBytes(), no.toBytes()],
+        program.programId
+      )[0],
+    };
+  };
+
+  before(async () => {
+    await ensureTestConfig(provider, program, amm.programId);
+
+    ({ yesToken, noToken, market } = await createMarket(false));
+  });
+
+  it("halts only the graduated side", async () => {
+    await swap(GRADUATION_THRESHOLD * 1.1, 0, 1);
+    const account = await program.account.market.fetch(market);
+    expect(account.yesCompleted).to.be.true;
+    expect(account.noCompleted).to.be.false;
+
+    try {
+      await swap(1_000_000, 0, 1);
+      expect.fail("graduated side traded");
+    } catch (err) {
+      expect(String(err)).to.contain("MarketAlreadyCompleted");
+    }
+    await swap(100_000_000, 0, 0);
+  });
+
+  it("rejects pools that are not pdas of the amm program", async () => {
+    try {
+      await migrate(Keypair.generate().publicKey, pool(noToken));
+      expect.fail("migrated into a foreign pool");
+    } catch (err) {
+      expect(String(err)).to.contain("ConstraintSeeds");
+    }
+  });
+
+  it("moves both curves into the amm", async () => {
+    const before = await program.account.market.fetch(market);
+    await migrate(pool(yesToken), pool(noToken));
+
+    const yesPool = await amm.account.pool.fetch(pool(yesToken));
+    expect(yesPool.mint.equals(yesToken)).to.be.true;
+    expect(yesPool.tokenReserve.eq(before.realYesTokenReserves)).to.be.true;
+    expect(yesPool.solReserve.eq(before.realYesSolReserves)).to.be.true;
+
+    const noPool = await amm.account.pool.fetch(pool(noToken));
+    expect(noPool.tokenReserve.eq(before.realNoTokenReserves)).to.be.true;
+    expect(noPool.solReserve.eq(before.realNoSolReserves)).to.be.true;
+
+    const after = await program.account.market.fetch(market);
+    expect(after.isCompleted).to.be.true;
+    expect(after.realYesSolReserves.isZero()).to.be.true;
+    expect(after.realNoSolReserves.isZero()).to.be.true;
+
+    try {
+      await swap(1_000_000, 0, 0);
+      expect.fail("migrated market traded");
+    } catch (err) {
+      expect(String(err)).to.contain("MarketAlreadyCompleted");
+    }
+  });
+
+  it("keeps soulbound markets out of the amm", async () => {
+    const soulbound = await createMarket(true);
+    await send(
+      await swapTx(
+        admin.publicKey,
+        soulbound.yesToken,
+        soulbound.noToken,
+        GRADUATION_THRESHOLD * 1.1,
+        0,
+        1,
+        connection,
+        program,
+        tokenProgram
+      )
+    );
+
+    try {
+      await migrate(pool(soulbound.yesToken), pool(soulbound.noToken), soulbound);
+      expect.fail("migrated soulbound tokens");
+    } catch (err) {
+      expect(String(err)).to.contain("SoulboundMigration");
+    }
+  });
+});